@@ -0,0 +1,40 @@
+use crate::config::Config;
+use crate::errors::Errors;
+use crate::file_root::FileRoot;
+use crate::mime::MimeRegistry;
+use crate::path_lock::PathLocks;
+
+// ---- -- App State -- ---- \\
+
+/// Estado compartido por todos los handlers del servidor.
+///
+/// Se crea una sola vez al arrancar y se comparte entre conexiones
+/// con un `Arc`, los handlers lo reciben en cada solicitud.
+#[derive(Debug)]
+pub struct AppState {
+    pub config: Config,
+    pub file_root: FileRoot,
+    pub mime: MimeRegistry,
+    // Archivos que una solicitud está subiendo o borrando
+    pub path_locks: PathLocks,
+}
+
+impl AppState {
+    /// Falla si el directorio de la configuración no existe.
+    pub fn new(config: Config) -> Result<Self, Errors> {
+        let file_root =
+            FileRoot::new(&config.directory, config.follow_symlinks)?;
+
+        let mut mime = MimeRegistry::new(config.sniff_mime_types);
+        for (extension, mime_type) in &config.mime_types {
+            mime.insert(extension, mime_type);
+        }
+
+        Ok(AppState {
+            config,
+            file_root,
+            mime,
+            path_locks: PathLocks::new(),
+        })
+    }
+}
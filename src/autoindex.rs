@@ -0,0 +1,380 @@
+use std::io;
+use std::path::Path;
+
+use crate::file_root::is_temp_file;
+use crate::http::{percent_encode_path, HeaderMap, HttpDate};
+
+// ---- -- Listing Format -- ---- \\
+
+/// Formato del listado de un directorio, se elige con `Accept`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListingFormat {
+    Html,
+    Json,
+}
+
+impl ListingFormat {
+    /// JSON solo si el cliente lo prefiere sobre HTML, cualquier otro
+    /// caso (sin `Accept` o sin ninguno de los dos) recibe HTML.
+    pub fn negotiate(headers: &HeaderMap) -> ListingFormat {
+        let accept = headers.accept();
+
+        if quality(&accept, "application/json") > quality(&accept, "text/html")
+        {
+            ListingFormat::Json
+        } else {
+            ListingFormat::Html
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ListingFormat::Html => "text/html; charset=utf-8",
+            ListingFormat::Json => "application/json",
+        }
+    }
+}
+
+/// q-value de `mime` en `Accept`, según el rango más específico que lo
+/// incluye ("text/html" antes que "text/*" y que "*/*").
+fn quality(accept: &[(&str, f32)], mime: &str) -> f32 {
+    let main_type = mime.split('/').next().unwrap_or("");
+
+    accept
+        .iter()
+        .filter_map(|(range, qvalue)| {
+            let specificity = match range.split_once('/') {
+                Some(("*", "*")) => 0,
+                Some((range_type, "*"))
+                    if range_type.eq_ignore_ascii_case(main_type) =>
+                {
+                    1
+                }
+                _ if range.eq_ignore_ascii_case(mime) => 2,
+                _ => return None,
+            };
+
+            Some((specificity, *qvalue))
+        })
+        .max_by_key(|(specificity, _)| *specificity)
+        .map_or(0.0, |(_, qvalue)| qvalue)
+}
+
+// ---- -- Directory Listing -- ---- \\
+
+/// Archivo o subdirectorio de un listado.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListingEntry {
+    pub name: String,
+    pub is_dir: bool,
+    // Tamaño en bytes, 0 para los directorios
+    pub size: u64,
+    pub modified: Option<HttpDate>,
+}
+
+/// Listado de un directorio para el modo `--autoindex`.
+///
+/// Los subdirectorios van primero y luego los archivos, ambos ordenados
+/// por nombre.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectoryListing {
+    // Ruta pedida por el cliente, termina en "/"
+    path: String,
+    entries: Vec<ListingEntry>,
+}
+
+impl DirectoryListing {
+    pub fn new(path: &str, mut entries: Vec<ListingEntry>) -> Self {
+        entries.sort_by(|a, b| {
+            b.is_dir.cmp(&a.is_dir).then_with(|| a.name.cmp(&b.name))
+        });
+
+        DirectoryListing {
+            path: path.to_string(),
+            entries,
+        }
+    }
+
+    /// Lee el contenido de `dir`, que se muestra como `path`.
+    ///
+    /// Los enlaces simbólicos se siguen como al servir los archivos, se
+    /// omiten los enlaces rotos, los nombres que no son UTF-8 y los
+    /// archivos temporales de una subida en curso.
+    pub async fn read(dir: &Path, path: &str) -> io::Result<Self> {
+        let mut read_dir = tokio::fs::read_dir(dir).await?;
+        let mut entries = Vec::new();
+
+        while let Some(entry) = read_dir.next_entry().await? {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if is_temp_file(Path::new(&name)) {
+                continue;
+            }
+            let Ok(metadata) = tokio::fs::metadata(entry.path()).await else {
+                continue;
+            };
+
+            entries.push(ListingEntry {
+                name,
+                is_dir: metadata.is_dir(),
+                size: if metadata.is_dir() { 0 } else { metadata.len() },
+                modified: metadata.modified().ok().map(HttpDate::from),
+            });
+        }
+
+        Ok(DirectoryListing::new(path, entries))
+    }
+
+    /// Conserva solo las entradas para las que `keep` devuelve `true`.
+    pub fn retain<F>(&mut self, keep: F)
+    where
+        F: FnMut(&ListingEntry) -> bool,
+    {
+        self.entries.retain(keep);
+    }
+
+    pub fn render(&self, format: ListingFormat) -> String {
+        match format {
+            ListingFormat::Html => self.to_html(),
+            ListingFormat::Json => self.to_json(),
+        }
+    }
+
+    pub fn to_html(&self) -> String {
+        let title = format!("Index of {}", escape_html(&self.path));
+        let mut rows = String::new();
+
+        if self.path != "/" {
+            rows.push_str(&row("../", "../", "-", "-"));
+        }
+
+        for entry in &self.entries {
+            let suffix = if entry.is_dir { "/" } else { "" };
+            let size = match entry.is_dir {
+                true => "-".to_string(),
+                false => entry.size.to_string(),
+            };
+            let modified = entry
+                .modified
+                .map_or("-".to_string(), |date| date.to_string());
+
+            // "./" evita que un nombre como "a:b" se lea como un esquema
+            let href =
+                format!("./{}{}", percent_encode_path(&entry.name), suffix);
+
+            let name = format!("{}{}", escape_html(&entry.name), suffix);
+            rows.push_str(&row(&escape_html(&href), &name, &size, &modified));
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n\
+             <title>{}</title>\n</head>\n<body>\n<h1>{}</h1>\n<table>\n\
+             <tr><th>Name</th><th>Size</th><th>Last modified</th></tr>\n\
+             {}</table>\n</body>\n</html>\n",
+            title, title, rows
+        )
+    }
+
+    pub fn to_json(&self) -> String {
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let kind = if entry.is_dir { "directory" } else { "file" };
+                let modified =
+                    entry.modified.map_or("null".to_string(), |date| {
+                        escape_json(&date.to_string())
+                    });
+
+                format!(
+                    "{{\"name\":{},\"type\":\"{}\",\"size\":{},\
+                     \"modified\":{}}}",
+                    escape_json(&entry.name),
+                    kind,
+                    entry.size,
+                    modified
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\"path\":{},\"entries\":[{}]}}",
+            escape_json(&self.path),
+            entries.join(",")
+        )
+    }
+}
+
+/// Fila de la tabla del listado, los valores ya deben estar escapados.
+fn row(href: &str, name: &str, size: &str, modified: &str) -> String {
+    format!(
+        "<tr><td><a href=\"{}\">{}</a></td><td>{}</td><td>{}</td></tr>\n",
+        href, name, size, modified
+    )
+}
+
+/// Escapa los caracteres especiales de HTML.
+fn escape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// String JSON entre comillas, con los caracteres especiales escapados.
+fn escape_json(text: &str) -> String {
+    let mut result = String::with_capacity(text.len() + 2);
+    result.push('"');
+
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_control() && (c as u32) < 0x20 => {
+                result.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => result.push(c),
+        }
+    }
+
+    result.push('"');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, is_dir: bool, size: u64) -> ListingEntry {
+        ListingEntry {
+            name: name.to_string(),
+            is_dir,
+            size,
+            modified: HttpDate::parse("Sun, 06 Nov 1994 08:49:37 GMT"),
+        }
+    }
+
+    fn listing() -> DirectoryListing {
+        DirectoryListing::new(
+            "/files/sub/",
+            vec![
+                entry("b.txt", false, 10),
+                entry("z", true, 0),
+                entry("a <b>&\"c\".html", false, 3),
+                entry("assets", true, 0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_listing_is_sorted() {
+        let listing = listing();
+        let names: Vec<&str> = listing
+            .entries
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect();
+
+        assert_eq!(names, ["assets", "z", "a <b>&\"c\".html", "b.txt"]);
+    }
+
+    #[test]
+    fn test_to_html() {
+        let html = listing().to_html();
+
+        assert!(html.contains("<title>Index of /files/sub/</title>"));
+        assert!(html.contains("<a href=\"../\">../</a>"));
+        assert!(html.contains("<a href=\"./assets/\">assets/</a>"));
+        assert!(html.contains(
+            "<a href=\"./a%20%3Cb%3E&amp;%22c%22.html\">\
+             a &lt;b&gt;&amp;&quot;c&quot;.html</a></td><td>3</td>\
+             <td>Sun, 06 Nov 1994 08:49:37 GMT</td>"
+        ));
+        assert!(!html.contains("<b>"));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let mut listing = listing();
+        listing.retain(|entry| entry.name != "z" && entry.name != "assets");
+
+        assert_eq!(
+            listing.to_json(),
+            "{\"path\":\"/files/sub/\",\"entries\":[\
+             {\"name\":\"a <b>&\\\"c\\\".html\",\"type\":\"file\",\"size\":3,\
+             \"modified\":\"Sun, 06 Nov 1994 08:49:37 GMT\"},\
+             {\"name\":\"b.txt\",\"type\":\"file\",\"size\":10,\
+             \"modified\":\"Sun, 06 Nov 1994 08:49:37 GMT\"}]}"
+        );
+    }
+
+    #[test]
+    fn test_escape_json_control_chars() {
+        assert_eq!(escape_json("a\\b\n\u{1}"), "\"a\\\\b\\n\\u0001\"");
+    }
+
+    #[test]
+    fn test_negotiate_format() {
+        let cases = [
+            (None, ListingFormat::Html),
+            (Some("application/json"), ListingFormat::Json),
+            (Some("text/html, application/json"), ListingFormat::Html),
+            (
+                Some("text/html;q=0.5, application/json"),
+                ListingFormat::Json,
+            ),
+            (Some("application/*, text/*;q=0.1"), ListingFormat::Json),
+            (Some("*/*, text/html;q=0"), ListingFormat::Json),
+            (Some("image/png"), ListingFormat::Html),
+        ];
+
+        for (accept, expected) in cases {
+            let headers = match accept {
+                Some(accept) => HeaderMap::from([("Accept", accept)]),
+                None => HeaderMap::new(),
+            };
+
+            assert_eq!(
+                ListingFormat::negotiate(&headers),
+                expected,
+                "{:?}",
+                accept
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_directory() {
+        let dir = std::env::temp_dir()
+            .join(format!("autoindex_test_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.txt"), "abc").unwrap();
+        // Temporal de una subida de "a.txt" que todavía no terminó
+        std::fs::write(dir.join(".a.txt.1-0.tmp"), "ab").unwrap();
+
+        let listing = DirectoryListing::read(&dir, "/files/").await.unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(listing.entries.len(), 2);
+        assert_eq!(listing.entries[0].name, "sub");
+        assert!(listing.entries[0].is_dir);
+        assert_eq!(listing.entries[1].name, "a.txt");
+        assert_eq!(listing.entries[1].size, 3);
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// ---- -- Config -- ---- \\
+
+/// Opciones del servidor recibidas por línea de comandos.
+#[derive(Debug, Clone)]
+pub struct Config {
+    // Directorio desde el que se sirven los archivos de "/files"
+    pub directory: PathBuf,
+    // Seguir enlaces simbólicos que apuntan fuera de `directory`
+    pub follow_symlinks: bool,
+    // Tamaño mínimo (en bytes) de un body para comprimirlo, con 0 se
+    // comprime cualquier body que no esté vacío
+    pub compress_min_size: u64,
+    // Tipos MIME por extensión que reemplazan a los predefinidos
+    pub mime_types: Vec<(String, String)>,
+    // Adivinar el tipo de los archivos sin extensión conocida a partir
+    // de su contenido
+    pub sniff_mime_types: bool,
+    // Listar el contenido de los directorios que no tienen index.html
+    pub autoindex: bool,
+    // No registrar las rutas que modifican los archivos de "/files"
+    pub read_only: bool,
+    // Puerto en el que escucha el servidor
+    pub port: u16,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            directory: PathBuf::from("."),
+            follow_symlinks: false,
+            compress_min_size: 0,
+            mime_types: Vec::new(),
+            sniff_mime_types: true,
+            autoindex: false,
+            read_only: false,
+            port: 4221,
+        }
+    }
+}
+
+impl Config {
+    /// Lee las opciones de `args`, el primer elemento es el binario.
+    ///
+    /// Opciones soportadas:
+    /// * `--directory <dir>`
+    /// * `--follow-symlinks`
+    /// * `--compress-min-size <bytes>`
+    /// * `--mime-type <ext>=<tipo>` (se puede repetir)
+    /// * `--no-mime-sniffing`
+    /// * `--autoindex`
+    /// * `--read-only`
+    /// * `--port <puerto>`
+    pub fn from_args(args: Vec<String>) -> Config {
+        let mut config = Config::default();
+        let mut args = args.into_iter().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--directory" => {
+                    let arg_dir =
+                        args.next().expect("Expected --directory argument");
+
+                    // convertir file en Path
+                    let arg_dir = PathBuf::from_str(&arg_dir)
+                        .expect("Failed to parse file path");
+
+                    if !arg_dir.is_dir() {
+                        panic!("Expected directory path");
+                    }
+
+                    config.directory = arg_dir;
+                }
+                "--follow-symlinks" => config.follow_symlinks = true,
+                "--compress-min-size" => {
+                    config.compress_min_size = args
+                        .next()
+                        .and_then(|size| size.parse().ok())
+                        .expect("Expected --compress-min-size <bytes>");
+                }
+                "--mime-type" => {
+                    let mime_type = args
+                        .next()
+                        .and_then(|arg| {
+                            let (extension, mime) = arg.split_once('=')?;
+                            Some((extension.to_string(), mime.to_string()))
+                        })
+                        .expect("Expected --mime-type <ext>=<type>");
+
+                    config.mime_types.push(mime_type);
+                }
+                "--no-mime-sniffing" => config.sniff_mime_types = false,
+                "--autoindex" => config.autoindex = true,
+                "--read-only" => config.read_only = true,
+                "--port" => {
+                    config.port = args
+                        .next()
+                        .and_then(|port| port.parse().ok())
+                        .expect("Expected --port <port>");
+                }
+                _ => panic!("Unknown argument: {}", arg),
+            }
+        }
+
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_config_without_args() {
+        let config = Config::from_args(args(&["server"]));
+
+        assert_eq!(config.directory, PathBuf::from("."));
+        assert!(!config.follow_symlinks);
+        assert_eq!(config.compress_min_size, 0);
+        assert!(config.mime_types.is_empty());
+        assert!(config.sniff_mime_types);
+        assert!(!config.autoindex);
+        assert!(!config.read_only);
+        assert_eq!(config.port, 4221);
+    }
+
+    #[test]
+    fn test_config_with_args() {
+        let config = Config::from_args(args(&[
+            "server",
+            "--follow-symlinks",
+            "--directory",
+            "src",
+            "--compress-min-size",
+            "1024",
+            "--mime-type",
+            "rs=text/x-rust",
+            "--mime-type",
+            ".md=text/plain",
+            "--no-mime-sniffing",
+            "--autoindex",
+            "--read-only",
+            "--port",
+            "8080",
+        ]));
+
+        assert_eq!(config.directory, PathBuf::from("src"));
+        assert!(config.follow_symlinks);
+        assert_eq!(config.compress_min_size, 1024);
+        assert_eq!(
+            config.mime_types,
+            [
+                ("rs".to_string(), "text/x-rust".to_string()),
+                (".md".to_string(), "text/plain".to_string())
+            ]
+        );
+        assert!(!config.sniff_mime_types);
+        assert!(config.autoindex);
+        assert!(config.read_only);
+        assert_eq!(config.port, 8080);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected --mime-type")]
+    fn test_config_invalid_mime_type() {
+        Config::from_args(args(&["server", "--mime-type", "text/plain"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unknown argument")]
+    fn test_config_unknown_arg() {
+        Config::from_args(args(&["server", "--verbose"]));
+    }
+}
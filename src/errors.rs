@@ -1,8 +1,99 @@
 use thiserror::Error;
 
+use crate::http::StatusCode;
+
 #[derive(Error, Debug)]
 pub enum Errors {
     // ---- -- ParseUrl -- ---- //
     #[error("No se pudo parsear la URL: {0}.")]
     UrlParseError(String),
+
+    // ---- -- RequestReader -- ---- //
+    #[error("No se pudo leer del socket: {0}.")]
+    ReadError(#[from] std::io::Error),
+
+    #[error("El cliente no envió datos a tiempo.")]
+    ReadTimeout,
+
+    #[error("El cliente no terminó de enviar la solicitud a tiempo.")]
+    RequestTimeout,
+
+    #[error("La conexión se cerró antes de completar la solicitud.")]
+    IncompleteRequest,
+
+    #[error("El valor de Content-Length no es válido: {0}.")]
+    InvalidContentLength(String),
+
+    #[error("El body de {0} bytes supera el tamaño permitido.")]
+    PayloadTooLarge(usize),
+
+    #[error("Las cabeceras de la solicitud superan el tamaño permitido.")]
+    HeadersTooLarge,
+
+    #[error("El body codificado en chunks no es válido.")]
+    InvalidChunkedBody,
+
+    #[error("Transfer-Encoding no soportado: {0}.")]
+    UnsupportedTransferEncoding(String),
+
+    // ---- -- Parser -- ---- //
+    #[error("La solicitud HTTP no tiene un formato válido.")]
+    MalformedRequest,
+
+    #[error("La URI no es válida: {0}.")]
+    InvalidUri(String),
+
+    #[error("La URI de {0} bytes supera el tamaño permitido.")]
+    UriTooLong(usize),
+
+    // ---- -- Content-Encoding -- ---- //
+    #[error("Content-Encoding no soportado: {0}.")]
+    UnsupportedContentEncoding(String),
+
+    #[error("El body no se pudo decodificar como {0}.")]
+    InvalidContentEncoding(String),
+
+    #[error("El body decodificado supera los {0} bytes permitidos.")]
+    DecodedBodyTooLarge(usize),
+
+    // ---- -- FileRoot -- ---- //
+    #[error("La ruta está fuera del directorio permitido: {0}.")]
+    PathOutsideRoot(String),
+
+    #[error("La ruta no corresponde a un nombre de archivo válido: {0}.")]
+    InvalidFileName(String),
+
+    // ---- -- StatusCode -- ---- //
+    #[error("Código de estado desconocido: {0}.")]
+    UnknownStatusCode(u16),
+}
+
+impl Errors {
+    /// Código de estado con el que se debe responder al cliente,
+    /// `None` si la conexión debe cerrarse sin responder.
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match self {
+            Errors::RequestTimeout => Some(StatusCode::RequestTimeout),
+            Errors::InvalidContentLength(_) => Some(StatusCode::BadRequest),
+            Errors::InvalidChunkedBody => Some(StatusCode::BadRequest),
+            Errors::MalformedRequest => Some(StatusCode::BadRequest),
+            Errors::InvalidUri(_) => Some(StatusCode::BadRequest),
+            Errors::UriTooLong(_) => Some(StatusCode::UriTooLong),
+            Errors::PayloadTooLarge(_) => Some(StatusCode::ContentTooLarge),
+            Errors::HeadersTooLarge => {
+                Some(StatusCode::RequestHeaderFieldsTooLarge)
+            }
+            Errors::UnsupportedTransferEncoding(_) => {
+                Some(StatusCode::NotImplemented)
+            }
+            Errors::UnsupportedContentEncoding(_) => {
+                Some(StatusCode::UnsupportedMediaType)
+            }
+            Errors::InvalidContentEncoding(_) => Some(StatusCode::BadRequest),
+            Errors::DecodedBodyTooLarge(_) => Some(StatusCode::ContentTooLarge),
+            Errors::PathOutsideRoot(_) => Some(StatusCode::Forbidden),
+            Errors::InvalidFileName(_) => Some(StatusCode::BadRequest),
+            _ => None,
+        }
+    }
 }
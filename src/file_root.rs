@@ -0,0 +1,399 @@
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+
+use crate::errors::Errors;
+use crate::http::percent_decode;
+
+// Contador para que dos escrituras simultáneas del mismo archivo no
+// usen el mismo archivo temporal.
+static TEMP_FILE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+// ---- -- File Root -- ---- \\
+
+/// Directorio raíz desde el que se sirven y guardan archivos.
+///
+/// Toda ruta pedida se resuelve dentro de la raíz, las que intentan salir
+/// de ella (con "..", rutas absolutas o enlaces simbólicos que apuntan
+/// afuera) se rechazan con `Errors::PathOutsideRoot`.
+#[derive(Debug, Clone)]
+pub struct FileRoot {
+    root: PathBuf,
+    // Si es `true` los enlaces simbólicos se siguen aunque apunten
+    // fuera de la raíz
+    follow_symlinks: bool,
+}
+
+impl FileRoot {
+    pub fn new(root: &Path, follow_symlinks: bool) -> Result<Self, Errors> {
+        let root = root.canonicalize()?;
+
+        Ok(FileRoot {
+            root,
+            follow_symlinks,
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resuelve `relative`, una ruta sin decodificar como llega en la
+    /// URI, dentro de la raíz.
+    ///
+    /// Cada segmento se decodifica por separado y no puede contener una
+    /// "/" codificada (`Errors::InvalidFileName`). La ruta resultante
+    /// puede no existir todavía (por ejemplo al crear un archivo), pero
+    /// la parte que existe no puede salir de la raíz.
+    pub fn resolve(&self, relative: &str) -> Result<PathBuf, Errors> {
+        let mut decoded = Vec::new();
+        for segment in relative.split('/') {
+            let segment = String::from_utf8(percent_decode(segment))
+                .map_err(|_| Errors::InvalidFileName(relative.to_string()))?;
+
+            if segment.contains(['/', '\0']) {
+                return Err(Errors::InvalidFileName(relative.to_string()));
+            }
+            decoded.push(segment);
+        }
+        let relative = decoded.join("/");
+
+        let outside = || Errors::PathOutsideRoot(relative.clone());
+
+        let is_normal = |component: Component| {
+            matches!(component, Component::Normal(_) | Component::CurDir)
+        };
+        if !Path::new(&relative).components().all(is_normal) {
+            return Err(outside());
+        }
+
+        let path = self.root.join(&relative);
+
+        if self.follow_symlinks {
+            return Ok(path);
+        }
+
+        let real_path = canonicalize_existing(&path).ok_or_else(outside)?;
+
+        if !real_path.starts_with(&self.root) {
+            return Err(outside());
+        }
+
+        Ok(real_path)
+    }
+}
+
+/// Escribe `contents` en `path` de forma atómica.
+///
+/// El contenido se escribe en un archivo temporal del mismo directorio
+/// que luego se renombra a `path`, así quien lee el archivo ve la
+/// versión anterior o la nueva completa, nunca una escritura a medias.
+pub async fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_path = write_temp(path, contents).await?;
+
+    let result = tokio::fs::rename(&temp_path, path).await;
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&temp_path).await;
+    }
+
+    result
+}
+
+/// Como `write_atomic`, pero solo crea `path`: si ya existe falla con
+/// `ErrorKind::AlreadyExists` y el archivo queda intacto.
+///
+/// El temporal se enlaza con `hard_link`, que a diferencia de `rename`
+/// no reemplaza un archivo creado mientras tanto por otro proceso.
+pub async fn write_new(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let temp_path = write_temp(path, contents).await?;
+
+    let result = tokio::fs::hard_link(&temp_path, path).await;
+    let _ = tokio::fs::remove_file(&temp_path).await;
+
+    result
+}
+
+// Escribe `contents` en un archivo temporal junto a `path` y devuelve
+// su ruta, si falla no deja el temporal.
+async fn write_temp(path: &Path, contents: &[u8]) -> io::Result<PathBuf> {
+    let (Some(parent), Some(file_name)) = (path.parent(), path.file_name())
+    else {
+        return Err(io::ErrorKind::InvalidInput.into());
+    };
+
+    let counter = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let temp_path = parent.join(format!(
+        ".{}.{}-{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        counter
+    ));
+
+    let result = async {
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&temp_path)
+            .await?;
+
+        file.write_all(contents).await?;
+        file.sync_all().await
+    }
+    .await;
+
+    match result {
+        Ok(()) => Ok(temp_path),
+        Err(error) => {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            Err(error)
+        }
+    }
+}
+
+/// Indica si `path` es un archivo temporal de `write_atomic`.
+///
+/// Mientras dura una escritura el temporal está dentro de la raíz, así
+/// que no se debe servir, listar ni modificar como un archivo más.
+pub fn is_temp_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+
+    let Some(rest) = name
+        .strip_prefix('.')
+        .and_then(|rest| rest.strip_suffix(".tmp"))
+    else {
+        return false;
+    };
+
+    let Some((original, suffix)) = rest.rsplit_once('.') else {
+        return false;
+    };
+
+    let is_number = |value: &str| {
+        !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit())
+    };
+
+    match suffix.split_once('-') {
+        Some((pid, counter)) => {
+            !original.is_empty() && is_number(pid) && is_number(counter)
+        }
+        None => false,
+    }
+}
+
+/// Canonicaliza la parte de `path` que existe y le agrega el resto.
+///
+/// Devuelve `None` si `path` pasa por un enlace simbólico roto, ya que
+/// escribir en él crearía el archivo en el destino del enlace.
+fn canonicalize_existing(path: &Path) -> Option<PathBuf> {
+    for ancestor in path.ancestors() {
+        match ancestor.canonicalize() {
+            Ok(real_path) => {
+                let rest = path.strip_prefix(ancestor).ok()?;
+
+                // `join` con una ruta vacía agregaría una "/" al final
+                if rest.as_os_str().is_empty() {
+                    return Some(real_path);
+                }
+
+                return Some(real_path.join(rest));
+            }
+            Err(_) if ancestor.symlink_metadata().is_ok() => return None,
+            Err(_) => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs;
+    use std::os::unix::fs::symlink;
+
+    /// Crea un directorio temporal vacío para cada test.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "file_root_{}_{}",
+            name,
+            std::process::id()
+        ));
+
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("root/sub")).unwrap();
+        fs::create_dir_all(dir.join("outside")).unwrap();
+        fs::write(dir.join("root/sub/a.txt"), "a").unwrap();
+        fs::write(dir.join("outside/secret.txt"), "secret").unwrap();
+
+        dir
+    }
+
+    #[test]
+    fn test_resolve_inside_root() {
+        let dir = temp_dir("inside");
+        let file_root = FileRoot::new(&dir.join("root"), false).unwrap();
+
+        let existing = file_root.resolve("sub/a.txt").unwrap();
+        let new_file = file_root.resolve("sub/new/b.txt").unwrap();
+
+        assert_eq!(existing, file_root.root().join("sub/a.txt"));
+        assert_eq!(fs::read_to_string(existing).unwrap(), "a");
+        assert_eq!(new_file, file_root.root().join("sub/new/b.txt"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_rejects_parent_and_absolute_paths() {
+        let dir = temp_dir("lexical");
+        let file_root = FileRoot::new(&dir.join("root"), true).unwrap();
+
+        for relative in ["../outside/secret.txt", "sub/../../x", "/etc/passwd"]
+        {
+            let err = file_root.resolve(relative).unwrap_err();
+
+            assert!(matches!(err, Errors::PathOutsideRoot(_)), "{}", relative);
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_decodes_each_segment() {
+        let dir = temp_dir("decode");
+        fs::write(dir.join("root/sub/a b.txt"), "ab").unwrap();
+        let file_root = FileRoot::new(&dir.join("root"), true).unwrap();
+
+        let path = file_root.resolve("sub/a%20b.txt").unwrap();
+        assert_eq!(fs::read_to_string(path).unwrap(), "ab");
+
+        for relative in ["sub%2Fa.txt", "..%2F..%2Fetc%2Fpasswd", "a%00"] {
+            let err = file_root.resolve(relative).unwrap_err();
+
+            assert!(matches!(err, Errors::InvalidFileName(_)), "{}", relative);
+        }
+
+        // Un ".." codificado sigue sin poder salir de la raíz
+        let err = file_root.resolve("%2e%2e/outside/secret.txt").unwrap_err();
+        assert!(matches!(err, Errors::PathOutsideRoot(_)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_symlink_outside_root() {
+        let dir = temp_dir("symlink");
+        let root = dir.join("root");
+        symlink(dir.join("outside"), root.join("link")).unwrap();
+        symlink(dir.join("outside/secret.txt"), root.join("secret")).unwrap();
+        symlink(dir.join("outside/missing"), root.join("dangling")).unwrap();
+
+        let file_root = FileRoot::new(&root, false).unwrap();
+
+        for relative in
+            ["link/secret.txt", "link/new.txt", "secret", "dangling"]
+        {
+            let err = file_root.resolve(relative).unwrap_err();
+
+            assert!(matches!(err, Errors::PathOutsideRoot(_)), "{}", relative);
+        }
+
+        // Con `follow_symlinks` el enlace se sigue
+        let file_root = FileRoot::new(&root, true).unwrap();
+        let path = file_root.resolve("link/secret.txt").unwrap();
+
+        assert_eq!(fs::read_to_string(path).unwrap(), "secret");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_symlink_inside_root() {
+        let dir = temp_dir("symlink_inside");
+        let root = dir.join("root");
+        symlink(root.join("sub"), root.join("link")).unwrap();
+
+        let file_root = FileRoot::new(&root, false).unwrap();
+        let path = file_root.resolve("link/a.txt").unwrap();
+
+        assert_eq!(path, file_root.root().join("sub/a.txt"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic() {
+        let dir = temp_dir("write_atomic");
+        let path = dir.join("root/image.bin");
+        let contents: Vec<u8> = (0..=255).collect();
+
+        write_atomic(&path, &contents).await.unwrap();
+        assert_eq!(fs::read(&path).unwrap(), contents);
+
+        write_atomic(&path, b"").await.unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"");
+
+        // No quedan archivos temporales junto al archivo escrito
+        let names: Vec<_> = fs::read_dir(dir.join("root"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names.len(), 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_new() {
+        let dir = temp_dir("write_new");
+        let path = dir.join("root/new.txt");
+
+        write_new(&path, b"mango").await.unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"mango");
+
+        let error = write_new(&path, b"banana").await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read(&path).unwrap(), b"mango");
+
+        // Tampoco quedan temporales cuando falla
+        let names: Vec<_> = fs::read_dir(dir.join("root"))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect();
+        assert_eq!(names.len(), 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_is_temp_file() {
+        for name in [".a.txt.12-0.tmp", "sub/.index.html.1-25.tmp"] {
+            assert!(is_temp_file(Path::new(name)), "{}", name);
+        }
+
+        for name in ["a.txt", ".a.txt", ".a.txt.tmp", ".12-0.tmp", "a.1-2.tmp"]
+        {
+            assert!(!is_temp_file(Path::new(name)), "{}", name);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_atomic_missing_directory() {
+        let dir = temp_dir("write_atomic_missing");
+        let path = dir.join("root/missing/a.txt");
+
+        let error = write_atomic(&path, b"a").await.unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+        assert!(!dir.join("root/missing").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
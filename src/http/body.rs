@@ -0,0 +1,211 @@
+use std::fmt;
+use std::io::{Error, ErrorKind, Result};
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+// Tamaño máximo de cada chunk al enviar un body en streaming.
+const CHUNK_SIZE: usize = 1024 * 16;
+
+// ---- -- Body -- ---- \\
+
+/// Cuerpo de una respuesta HTTP.
+///
+/// `Stream` permite enviar contenido sin cargarlo completo en memoria,
+/// si no se conoce su longitud se envía con `Transfer-Encoding: chunked`.
+pub enum Body {
+    Empty,
+    Full(Vec<u8>),
+    Stream {
+        reader: Box<dyn AsyncRead + Send + Unpin>,
+        length: Option<u64>,
+    },
+}
+
+impl Body {
+    pub fn stream<R>(reader: R, length: Option<u64>) -> Body
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        Body::Stream {
+            reader: Box::new(reader),
+            length,
+        }
+    }
+
+    /// Longitud del body, `None` si no se conoce de antemano.
+    pub fn length(&self) -> Option<u64> {
+        match self {
+            Body::Empty => Some(0),
+            Body::Full(bytes) => Some(bytes.len() as u64),
+            Body::Stream { length, .. } => *length,
+        }
+    }
+
+    /// Escribe el body en `writer`, esperando a que cada parte sea
+    /// aceptada antes de leer la siguiente.
+    pub async fn write_to<W>(self, writer: &mut W) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        match self {
+            Body::Empty => Ok(()),
+            Body::Full(bytes) => writer.write_all(&bytes).await,
+            Body::Stream {
+                reader,
+                length: Some(length),
+            } => {
+                let copied =
+                    tokio::io::copy(&mut reader.take(length), writer).await?;
+
+                if copied < length {
+                    // Ya se envió el Content-Length, no hay forma de
+                    // completar la respuesta
+                    return Err(Error::new(
+                        ErrorKind::UnexpectedEof,
+                        "El body terminó antes de Content-Length",
+                    ));
+                }
+
+                Ok(())
+            }
+            Body::Stream {
+                mut reader,
+                length: None,
+            } => {
+                let mut buffer = vec![0; CHUNK_SIZE];
+
+                loop {
+                    let bytes_read = reader.read(&mut buffer).await?;
+
+                    if bytes_read == 0 {
+                        break;
+                    }
+
+                    let size = format!("{:X}\r\n", bytes_read);
+                    writer.write_all(size.as_bytes()).await?;
+                    writer.write_all(&buffer[..bytes_read]).await?;
+                    writer.write_all(b"\r\n").await?;
+                }
+
+                writer.write_all(b"0\r\n\r\n").await
+            }
+        }
+    }
+
+    /// Escribe el contenido del body sin ningún delimitador, para las
+    /// respuestas que marcan su final cerrando la conexión.
+    pub async fn write_unframed<W>(self, writer: &mut W) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        match self {
+            Body::Stream {
+                mut reader,
+                length: None,
+            } => tokio::io::copy(&mut reader, writer).await.map(|_| ()),
+            body => body.write_to(writer).await,
+        }
+    }
+}
+
+impl From<Vec<u8>> for Body {
+    fn from(bytes: Vec<u8>) -> Body {
+        Body::Full(bytes)
+    }
+}
+
+impl fmt::Debug for Body {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Body::Empty => write!(f, "Empty"),
+            Body::Full(bytes) => f.debug_tuple("Full").field(bytes).finish(),
+            Body::Stream { length, .. } => {
+                f.debug_struct("Stream").field("length", length).finish()
+            }
+        }
+    }
+}
+
+// Dos streams nunca se consideran iguales, su contenido no se puede
+// comparar sin consumirlos.
+impl PartialEq for Body {
+    fn eq(&self, other: &Body) -> bool {
+        match (self, other) {
+            (Body::Empty, Body::Empty) => true,
+            (Body::Full(a), Body::Full(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_write_full_body() {
+        let body = Body::from(b"Hello".to_vec());
+        let mut output = Vec::new();
+
+        body.write_to(&mut output).await.unwrap();
+
+        assert_eq!(output, b"Hello");
+    }
+
+    #[tokio::test]
+    async fn test_write_stream_with_length() {
+        let body = Body::stream(&b"Hello, World!"[..], Some(5));
+        let mut output = Vec::new();
+
+        body.write_to(&mut output).await.unwrap();
+
+        assert_eq!(output, b"Hello");
+    }
+
+    #[tokio::test]
+    async fn test_write_stream_shorter_than_length() {
+        let body = Body::stream(&b"Hello"[..], Some(10));
+        let mut output = Vec::new();
+
+        let err = body.write_to(&mut output).await.unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[tokio::test]
+    async fn test_write_stream_chunked() {
+        let content = "a".repeat(CHUNK_SIZE + 10);
+        let body = Body::stream(std::io::Cursor::new(content.clone()), None);
+        let mut output = Vec::new();
+
+        body.write_to(&mut output).await.unwrap();
+
+        let expected = format!(
+            "{:X}\r\n{}\r\nA\r\n{}\r\n0\r\n\r\n",
+            CHUNK_SIZE,
+            &content[..CHUNK_SIZE],
+            &content[CHUNK_SIZE..]
+        );
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_write_stream_unframed() {
+        let body = Body::stream(&b"Hello"[..], None);
+        let mut output = Vec::new();
+
+        body.write_unframed(&mut output).await.unwrap();
+
+        assert_eq!(output, b"Hello");
+    }
+
+    #[tokio::test]
+    async fn test_write_stream_chunked_empty() {
+        let body = Body::stream(&b""[..], None);
+        let mut output = Vec::new();
+
+        body.write_to(&mut output).await.unwrap();
+
+        assert_eq!(output, b"0\r\n\r\n");
+    }
+}
@@ -0,0 +1,295 @@
+use std::fs::Metadata;
+
+use crate::http::{EntityTag, HeaderMap, HttpDate, HttpMethod};
+
+// ---- -- Validators -- ---- \\
+
+/// Validadores del estado actual de un archivo, se envían en `ETag` y
+/// `Last-Modified` y se comparan con las precondiciones de la solicitud.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Validators {
+    pub etag: EntityTag,
+    pub last_modified: Option<HttpDate>,
+}
+
+impl Validators {
+    pub fn from_metadata(metadata: &Metadata) -> Validators {
+        Validators {
+            etag: EntityTag::from_metadata(metadata),
+            last_modified: metadata.modified().ok().map(HttpDate::from),
+        }
+    }
+
+    /// Agrega `ETag` y `Last-Modified` a las cabeceras de una respuesta.
+    pub fn insert_into(&self, headers: &mut HeaderMap) {
+        headers.insert("ETag", &self.etag.to_string());
+
+        if let Some(last_modified) = self.last_modified {
+            headers.insert("Last-Modified", &last_modified.to_string());
+        }
+    }
+}
+
+// ---- -- Preconditions -- ---- \\
+
+/// Resultado de evaluar las precondiciones de una solicitud.
+#[derive(Debug, PartialEq)]
+pub enum Precondition {
+    // Se atiende la solicitud normalmente
+    Passed,
+    // 304, el cliente ya tiene la representación actual
+    NotModified,
+    // 412, el recurso cambió desde que el cliente lo vio
+    Failed,
+}
+
+/// Evalúa `If-Match`, `If-Unmodified-Since`, `If-None-Match` e
+/// `If-Modified-Since` en el orden de la RFC 9110 (sección 13.2.2).
+///
+/// `current` son los validadores del recurso, `None` si todavía no
+/// existe (por ejemplo al subir un archivo nuevo).
+pub fn evaluate_preconditions(
+    method: &HttpMethod,
+    headers: &HeaderMap,
+    current: Option<&Validators>,
+) -> Precondition {
+    let is_read = matches!(method, HttpMethod::Get | HttpMethod::Head);
+
+    // If-Match tiene prioridad sobre If-Unmodified-Since
+    if headers.contains_key("If-Match") {
+        if !if_match(headers, current) {
+            return Precondition::Failed;
+        }
+    } else if let Some(date) = header_date(headers, "If-Unmodified-Since") {
+        let last_modified = current.and_then(|current| current.last_modified);
+
+        if last_modified.is_some_and(|last_modified| last_modified > date) {
+            return Precondition::Failed;
+        }
+    }
+
+    // If-None-Match tiene prioridad sobre If-Modified-Since
+    if headers.contains_key("If-None-Match") {
+        if !if_none_match(headers, current) {
+            return match is_read {
+                true => Precondition::NotModified,
+                false => Precondition::Failed,
+            };
+        }
+    } else if let Some(date) = header_date(headers, "If-Modified-Since") {
+        let last_modified = current.and_then(|current| current.last_modified);
+
+        if is_read && last_modified.is_some_and(|modified| modified <= date) {
+            return Precondition::NotModified;
+        }
+    }
+
+    Precondition::Passed
+}
+
+/// `If-Match`: `*` si el recurso existe, o alguno de los ETags
+/// listados con comparación fuerte. El ETag de una representación
+/// comprimida también vale, identifica el mismo contenido.
+fn if_match(headers: &HeaderMap, current: Option<&Validators>) -> bool {
+    let Some(current) = current else {
+        return false;
+    };
+
+    headers.get_list("If-Match").any(|item| {
+        item == "*"
+            || EntityTag::parse(item).is_some_and(|tag| {
+                tag.without_coding().strong_eq(&current.etag)
+            })
+    })
+}
+
+/// `If-None-Match`: ninguno de los ETags listados coincide con
+/// comparación débil, `*` solo se cumple si el recurso no existe.
+fn if_none_match(headers: &HeaderMap, current: Option<&Validators>) -> bool {
+    let Some(current) = current else {
+        return true;
+    };
+
+    !headers.get_list("If-None-Match").any(|item| {
+        item == "*"
+            || EntityTag::parse(item)
+                .is_some_and(|tag| tag.without_coding().weak_eq(&current.etag))
+    })
+}
+
+/// Fecha de una cabecera, las fechas inválidas se ignoran.
+fn header_date(headers: &HeaderMap, name: &str) -> Option<HttpDate> {
+    HttpDate::parse(headers.get(name)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODIFIED: &str = "Sun, 06 Nov 1994 08:49:37 GMT";
+    const BEFORE: &str = "Sat, 05 Nov 1994 08:49:37 GMT";
+    const AFTER: &str = "Mon, 07 Nov 1994 08:49:37 GMT";
+
+    fn current() -> Validators {
+        Validators {
+            etag: EntityTag::strong("abc"),
+            last_modified: HttpDate::parse(MODIFIED),
+        }
+    }
+
+    fn evaluate(
+        method: HttpMethod,
+        headers: &[(&str, &str)],
+        current: Option<&Validators>,
+    ) -> Precondition {
+        let headers = HeaderMap::from_iter(headers.iter().copied());
+
+        evaluate_preconditions(&method, &headers, current)
+    }
+
+    #[test]
+    fn test_if_none_match() {
+        let current = current();
+        let cases = [
+            ("\"abc\"", Precondition::NotModified),
+            ("W/\"abc\"", Precondition::NotModified),
+            ("\"abc-deflate\"", Precondition::NotModified),
+            ("\"xyz\", \"abc\"", Precondition::NotModified),
+            ("*", Precondition::NotModified),
+            ("\"xyz\"", Precondition::Passed),
+            ("basura", Precondition::Passed),
+        ];
+
+        for (value, expected) in cases {
+            let headers = [("If-None-Match", value)];
+
+            assert_eq!(
+                evaluate(HttpMethod::Get, &headers, Some(&current)),
+                expected,
+                "{}",
+                value
+            );
+        }
+
+        // En métodos que modifican el recurso se responde 412
+        let headers = [("If-None-Match", "*")];
+        assert_eq!(
+            evaluate(HttpMethod::Post, &headers, Some(&current)),
+            Precondition::Failed
+        );
+        assert_eq!(
+            evaluate(HttpMethod::Post, &headers, None),
+            Precondition::Passed
+        );
+    }
+
+    #[test]
+    fn test_if_modified_since() {
+        let current = current();
+        let cases = [
+            (MODIFIED, Precondition::NotModified),
+            (AFTER, Precondition::NotModified),
+            (BEFORE, Precondition::Passed),
+            ("ayer", Precondition::Passed),
+        ];
+
+        for (value, expected) in cases {
+            let headers = [("If-Modified-Since", value)];
+
+            assert_eq!(
+                evaluate(HttpMethod::Get, &headers, Some(&current)),
+                expected,
+                "{}",
+                value
+            );
+        }
+
+        // Solo se evalúa en GET y HEAD
+        let headers = [("If-Modified-Since", AFTER)];
+        assert_eq!(
+            evaluate(HttpMethod::Post, &headers, Some(&current)),
+            Precondition::Passed
+        );
+    }
+
+    #[test]
+    fn test_if_match() {
+        let current = current();
+        let cases = [
+            ("\"abc\"", Precondition::Passed),
+            ("\"xyz\", \"abc\"", Precondition::Passed),
+            ("\"abc-gzip\"", Precondition::Passed),
+            ("*", Precondition::Passed),
+            ("W/\"abc\"", Precondition::Failed),
+            ("\"xyz\"", Precondition::Failed),
+        ];
+
+        for (value, expected) in cases {
+            let headers = [("If-Match", value)];
+
+            assert_eq!(
+                evaluate(HttpMethod::Post, &headers, Some(&current)),
+                expected,
+                "{}",
+                value
+            );
+        }
+
+        // Sin recurso no hay ETag que pueda coincidir
+        let headers = [("If-Match", "*")];
+        assert_eq!(
+            evaluate(HttpMethod::Post, &headers, None),
+            Precondition::Failed
+        );
+    }
+
+    #[test]
+    fn test_if_unmodified_since() {
+        let current = current();
+        let cases = [
+            (MODIFIED, Precondition::Passed),
+            (AFTER, Precondition::Passed),
+            (BEFORE, Precondition::Failed),
+            ("ayer", Precondition::Passed),
+        ];
+
+        for (value, expected) in cases {
+            let headers = [("If-Unmodified-Since", value)];
+
+            assert_eq!(
+                evaluate(HttpMethod::Get, &headers, Some(&current)),
+                expected,
+                "{}",
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_precedence() {
+        let current = current();
+
+        // If-Match reemplaza a If-Unmodified-Since
+        let headers =
+            [("If-Match", "\"abc\""), ("If-Unmodified-Since", BEFORE)];
+        assert_eq!(
+            evaluate(HttpMethod::Get, &headers, Some(&current)),
+            Precondition::Passed
+        );
+
+        // If-None-Match reemplaza a If-Modified-Since
+        let headers =
+            [("If-None-Match", "\"xyz\""), ("If-Modified-Since", AFTER)];
+        assert_eq!(
+            evaluate(HttpMethod::Get, &headers, Some(&current)),
+            Precondition::Passed
+        );
+
+        // Las precondiciones de escritura se evalúan antes que las de caché
+        let headers = [("If-Match", "\"xyz\""), ("If-None-Match", "\"abc\"")];
+        assert_eq!(
+            evaluate(HttpMethod::Get, &headers, Some(&current)),
+            Precondition::Failed
+        );
+    }
+}
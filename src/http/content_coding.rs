@@ -0,0 +1,414 @@
+use std::io::{self, Read, Write};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use flate2::read::{DeflateDecoder, MultiGzDecoder, ZlibDecoder};
+use flate2::write::{GzEncoder, ZlibEncoder};
+use flate2::Compression;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::errors::Errors;
+use crate::http::{Body, HeaderMap};
+
+// Tamaño de cada lectura al comprimir un body en streaming.
+const CHUNK_SIZE: usize = 1024 * 16;
+
+// ---- -- Content Coding -- ---- \\
+
+/// Codificación de contenido (`Content-Encoding`) soportada por el
+/// servidor.
+///
+/// `Deflate` usa el formato zlib, que es lo que HTTP llama "deflate"
+/// (RFC 9110, sección 8.4.1.2).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ContentCoding {
+    Gzip,
+    Deflate,
+    Identity,
+}
+
+impl ContentCoding {
+    // Orden de preferencia del servidor cuando el cliente acepta varias
+    // codificaciones con el mismo q-value.
+    const PREFERRED: [ContentCoding; 2] =
+        [ContentCoding::Gzip, ContentCoding::Deflate];
+
+    /// Codificación que corresponde a `token`, sin distinguir
+    /// mayúsculas. `None` si el servidor no la soporta.
+    pub fn from_token(token: &str) -> Option<ContentCoding> {
+        match token.to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(ContentCoding::Gzip),
+            "deflate" => Some(ContentCoding::Deflate),
+            "identity" => Some(ContentCoding::Identity),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentCoding::Gzip => "gzip",
+            ContentCoding::Deflate => "deflate",
+            ContentCoding::Identity => "identity",
+        }
+    }
+
+    /// Elige la codificación de la respuesta según la cabecera
+    /// `Accept-Encoding` de la solicitud (RFC 9110, sección 12.5.3).
+    ///
+    /// Gana la codificación soportada con mayor q-value, las que no se
+    /// listan toman el q-value de `*` y un q-value de 0 significa "no
+    /// aceptable". Si ninguna es aceptable se responde sin codificar.
+    pub fn negotiate(headers: &HeaderMap) -> ContentCoding {
+        let accepted = headers.accept_encoding();
+
+        let qvalue = |coding: ContentCoding| {
+            let listed = accepted.iter().find(|(token, _)| {
+                ContentCoding::from_token(token) == Some(coding)
+            });
+            let wildcard = accepted.iter().find(|(token, _)| *token == "*");
+
+            listed.or(wildcard).map_or(0.0, |(_, qvalue)| *qvalue)
+        };
+
+        let mut best = ContentCoding::Identity;
+        let mut best_qvalue = 0.0;
+
+        for coding in ContentCoding::PREFERRED {
+            let qvalue = qvalue(coding);
+
+            if qvalue > best_qvalue {
+                best = coding;
+                best_qvalue = qvalue;
+            }
+        }
+
+        best
+    }
+
+    /// Codifica `body`. Los streams se codifican a medida que se envían,
+    /// por lo que dejan de tener una longitud conocida.
+    pub fn encode_body(&self, body: Body) -> Body {
+        let Some(mut encoder) = Encoder::new(*self) else {
+            return body;
+        };
+
+        match body {
+            Body::Empty => Body::Empty,
+            Body::Full(bytes) => {
+                // Escribir en un `Vec` no puede fallar
+                encoder
+                    .write_all(&bytes)
+                    .expect("Failed to encode the body");
+                Body::Full(encoder.finish().expect("Failed to encode the body"))
+            }
+            Body::Stream { reader, .. } => {
+                Body::stream(EncodedReader::new(reader, encoder), None)
+            }
+        }
+    }
+
+    /// Decodifica `data`, fallando si el resultado supera `limit` bytes.
+    ///
+    /// El límite se controla mientras se descomprime, así un body chico
+    /// que se expande a gigas (zip bomb) nunca se carga completo.
+    pub fn decode(&self, data: &[u8], limit: usize) -> Result<Vec<u8>, Errors> {
+        let decoded = match self {
+            ContentCoding::Gzip => {
+                read_limited(MultiGzDecoder::new(data), limit)
+            }
+            // Algunos clientes envían "deflate" sin el envoltorio zlib
+            ContentCoding::Deflate => {
+                read_limited(ZlibDecoder::new(data), limit)
+                    .or_else(|_| read_limited(DeflateDecoder::new(data), limit))
+            }
+            ContentCoding::Identity => return Ok(data.to_vec()),
+        };
+
+        match decoded {
+            Ok(decoded) if decoded.len() > limit => {
+                Err(Errors::DecodedBodyTooLarge(limit))
+            }
+            Ok(decoded) => Ok(decoded),
+            Err(_) => Err(Errors::InvalidContentEncoding(self.as_str().into())),
+        }
+    }
+}
+
+/// Lee hasta `limit + 1` bytes de `reader`, el byte extra permite saber
+/// si el contenido supera el límite.
+fn read_limited<R: Read>(reader: R, limit: usize) -> io::Result<Vec<u8>> {
+    let mut decoded = Vec::new();
+    reader.take(limit as u64 + 1).read_to_end(&mut decoded)?;
+
+    Ok(decoded)
+}
+
+// ---- -- Encoder -- ---- \\
+
+/// Compresor que acumula su salida en memoria.
+enum Encoder {
+    Gzip(GzEncoder<Vec<u8>>),
+    Deflate(ZlibEncoder<Vec<u8>>),
+}
+
+impl Encoder {
+    fn new(coding: ContentCoding) -> Option<Encoder> {
+        let level = Compression::default();
+
+        match coding {
+            ContentCoding::Gzip => {
+                Some(Encoder::Gzip(GzEncoder::new(Vec::new(), level)))
+            }
+            ContentCoding::Deflate => {
+                Some(Encoder::Deflate(ZlibEncoder::new(Vec::new(), level)))
+            }
+            ContentCoding::Identity => None,
+        }
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> io::Result<()> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.write_all(data),
+            Encoder::Deflate(encoder) => encoder.write_all(data),
+        }
+    }
+
+    /// Salida producida hasta ahora, el compresor puede retener datos
+    /// hasta recibir más o hasta `finish`.
+    fn take_output(&mut self) -> Vec<u8> {
+        match self {
+            Encoder::Gzip(encoder) => std::mem::take(encoder.get_mut()),
+            Encoder::Deflate(encoder) => std::mem::take(encoder.get_mut()),
+        }
+    }
+
+    fn finish(self) -> io::Result<Vec<u8>> {
+        match self {
+            Encoder::Gzip(encoder) => encoder.finish(),
+            Encoder::Deflate(encoder) => encoder.finish(),
+        }
+    }
+}
+
+// ---- -- Encoded Reader -- ---- \\
+
+/// Lector que comprime el contenido de otro lector a medida que se lee.
+struct EncodedReader<R> {
+    reader: R,
+    // `None` una vez que el lector original terminó
+    encoder: Option<Encoder>,
+    input: Vec<u8>,
+    // Salida comprimida pendiente de entregar, desde `position`
+    output: Vec<u8>,
+    position: usize,
+}
+
+impl<R> EncodedReader<R> {
+    fn new(reader: R, encoder: Encoder) -> Self {
+        EncodedReader {
+            reader,
+            encoder: Some(encoder),
+            input: vec![0; CHUNK_SIZE],
+            output: Vec::new(),
+            position: 0,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for EncodedReader<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+
+        loop {
+            if this.position < this.output.len() {
+                let pending = &this.output[this.position..];
+                let size = pending.len().min(buf.remaining());
+
+                buf.put_slice(&pending[..size]);
+                this.position += size;
+
+                return Poll::Ready(Ok(()));
+            }
+
+            if this.encoder.is_none() {
+                return Poll::Ready(Ok(()));
+            }
+
+            let mut input = ReadBuf::new(&mut this.input);
+            ready!(Pin::new(&mut this.reader).poll_read(cx, &mut input))?;
+            let bytes_read = input.filled().len();
+
+            this.position = 0;
+            this.output = if bytes_read == 0 {
+                let encoder = this.encoder.take().unwrap();
+                encoder.finish()?
+            } else {
+                let encoder = this.encoder.as_mut().unwrap();
+                encoder.write_all(&this.input[..bytes_read])?;
+                encoder.take_output()
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Read;
+
+    use flate2::read::{GzDecoder, ZlibDecoder};
+    use tokio::io::AsyncReadExt;
+
+    fn negotiate(accept_encoding: &str) -> ContentCoding {
+        let headers = HeaderMap::from([("Accept-Encoding", accept_encoding)]);
+
+        ContentCoding::negotiate(&headers)
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let cases = [
+            ("gzip", ContentCoding::Gzip),
+            ("none, gzip, invalid", ContentCoding::Gzip),
+            ("invalid, none", ContentCoding::Identity),
+            ("gzip;q=0", ContentCoding::Identity),
+            ("gzip;q=0, deflate", ContentCoding::Deflate),
+            ("gzip;q=0.5, deflate;q=0.8", ContentCoding::Deflate),
+            ("deflate, gzip", ContentCoding::Gzip),
+            ("*", ContentCoding::Gzip),
+            ("*;q=0.5, gzip;q=0", ContentCoding::Deflate),
+            ("identity, *;q=0", ContentCoding::Identity),
+            ("X-GZIP;Q=0.1", ContentCoding::Gzip),
+            ("", ContentCoding::Identity),
+        ];
+
+        for (accept_encoding, expected) in cases {
+            assert_eq!(
+                negotiate(accept_encoding),
+                expected,
+                "{}",
+                accept_encoding
+            );
+        }
+
+        assert_eq!(
+            ContentCoding::negotiate(&HeaderMap::new()),
+            ContentCoding::Identity
+        );
+    }
+
+    #[test]
+    fn test_encode_full_body() {
+        let text = b"Wubba Lubba Dub Dub".repeat(10);
+
+        let Body::Full(gzip) =
+            ContentCoding::Gzip.encode_body(text.clone().into())
+        else {
+            panic!("Expected a full body");
+        };
+        let Body::Full(deflate) =
+            ContentCoding::Deflate.encode_body(text.clone().into())
+        else {
+            panic!("Expected a full body");
+        };
+
+        let mut decoded = Vec::new();
+        GzDecoder::new(&gzip[..]).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, text);
+
+        let mut decoded = Vec::new();
+        ZlibDecoder::new(&deflate[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, text);
+    }
+
+    #[tokio::test]
+    async fn test_encode_stream_body() {
+        let text = b"0123456789abcdef".repeat(5000);
+        let body = Body::stream(std::io::Cursor::new(text.clone()), None);
+
+        let Body::Stream { mut reader, length } =
+            ContentCoding::Gzip.encode_body(body)
+        else {
+            panic!("Expected a stream body");
+        };
+
+        let mut encoded = Vec::new();
+        reader.read_to_end(&mut encoded).await.unwrap();
+
+        let mut decoded = Vec::new();
+        GzDecoder::new(&encoded[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+
+        assert_eq!(length, None);
+        assert!(encoded.len() < text.len());
+        assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn test_decode() {
+        let text = b"Wubba Lubba Dub Dub".repeat(10);
+
+        for coding in [ContentCoding::Gzip, ContentCoding::Deflate] {
+            let Body::Full(encoded) = coding.encode_body(text.clone().into())
+            else {
+                panic!("Expected a full body");
+            };
+
+            assert_eq!(coding.decode(&encoded, 1024).unwrap(), text);
+        }
+    }
+
+    #[test]
+    fn test_decode_raw_deflate() {
+        let mut encoder = flate2::write::DeflateEncoder::new(
+            Vec::new(),
+            Compression::default(),
+        );
+        encoder.write_all(b"raw deflate").unwrap();
+        let encoded = encoder.finish().unwrap();
+
+        let decoded = ContentCoding::Deflate.decode(&encoded, 1024).unwrap();
+
+        assert_eq!(decoded, b"raw deflate");
+    }
+
+    #[test]
+    fn test_decode_over_limit() {
+        // 10 MB de ceros se comprimen a unos pocos KB
+        let Body::Full(bomb) =
+            ContentCoding::Gzip.encode_body(vec![0; 10 * 1024 * 1024].into())
+        else {
+            panic!("Expected a full body");
+        };
+
+        let err = ContentCoding::Gzip.decode(&bomb, 1024).unwrap_err();
+
+        assert!(bomb.len() < 64 * 1024);
+        assert!(matches!(err, Errors::DecodedBodyTooLarge(1024)));
+    }
+
+    #[test]
+    fn test_decode_invalid_data() {
+        let err = ContentCoding::Gzip.decode(b"not gzip", 1024).unwrap_err();
+
+        assert!(matches!(err, Errors::InvalidContentEncoding(_)));
+    }
+
+    #[test]
+    fn test_identity_keeps_body() {
+        let body = Body::from(b"abc".to_vec());
+
+        assert_eq!(
+            ContentCoding::Identity.encode_body(body),
+            b"abc".to_vec().into()
+        );
+    }
+}
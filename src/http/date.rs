@@ -0,0 +1,217 @@
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_DAY: u64 = 60 * 60 * 24;
+
+const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct",
+    "Nov", "Dec",
+];
+
+// ---- -- Http Date -- ---- \\
+
+/// Fecha de una cabecera HTTP (`Last-Modified`, `If-Modified-Since`...),
+/// con precisión de segundos.
+///
+/// Se envía en formato IMF-fixdate ("Sun, 06 Nov 1994 08:49:37 GMT") y
+/// al parsear también se aceptan los formatos obsoletos RFC 850 y
+/// asctime (RFC 9110, sección 5.6.7).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HttpDate {
+    // Segundos desde el 1 de enero de 1970
+    seconds: u64,
+}
+
+impl From<SystemTime> for HttpDate {
+    fn from(time: SystemTime) -> HttpDate {
+        // Las fechas anteriores a 1970 no se pueden representar
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+
+        HttpDate { seconds }
+    }
+}
+
+impl From<HttpDate> for SystemTime {
+    fn from(date: HttpDate) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(date.seconds)
+    }
+}
+
+impl HttpDate {
+    /// Parsea una fecha HTTP, `None` si no tiene ninguno de los
+    /// formatos válidos.
+    pub fn parse(value: &str) -> Option<HttpDate> {
+        let tokens: Vec<&str> = value.split_whitespace().collect();
+
+        let (day, month, year, time) = match tokens.as_slice() {
+            // IMF-fixdate: "Sun, 06 Nov 1994 08:49:37 GMT"
+            [_, day, month, year, time, "GMT"] => {
+                (day.parse().ok()?, *month, year.parse().ok()?, *time)
+            }
+            // RFC 850: "Sunday, 06-Nov-94 08:49:37 GMT"
+            [_, date, time, "GMT"] => {
+                let mut parts = date.split('-');
+                let day = parts.next()?.parse().ok()?;
+                let month = parts.next()?;
+                let year: i64 = parts.next()?.parse().ok()?;
+
+                // Los años de dos dígitos se interpretan como 1970-2069
+                let year = if year < 70 { year + 2000 } else { year + 1900 };
+
+                (day, month, year, *time)
+            }
+            // asctime: "Sun Nov  6 08:49:37 1994"
+            [_, month, day, time, year] => {
+                (day.parse().ok()?, *month, year.parse().ok()?, *time)
+            }
+            _ => return None,
+        };
+
+        let month = MONTHS.iter().position(|name| *name == month)? as u32 + 1;
+        if !(1..=31).contains(&day) {
+            return None;
+        }
+
+        let mut parts = time.split(':').map(|part| part.parse::<u64>().ok());
+        let (hour, minute, second) =
+            (parts.next()??, parts.next()??, parts.next()??);
+        if parts.next().is_some() || hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+
+        let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+        let seconds =
+            days * SECONDS_PER_DAY + hour * 3600 + minute * 60 + second;
+
+        Some(HttpDate { seconds })
+    }
+}
+
+impl fmt::Display for HttpDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let days = self.seconds / SECONDS_PER_DAY;
+        let time = self.seconds % SECONDS_PER_DAY;
+        let (year, month, day) = civil_from_days(days as i64);
+
+        // El 1 de enero de 1970 fue jueves
+        let weekday = WEEKDAYS[((days + 4) % 7) as usize];
+
+        write!(
+            f,
+            "{}, {:02} {} {} {:02}:{:02}:{:02} GMT",
+            weekday,
+            day,
+            MONTHS[month as usize - 1],
+            year,
+            time / 3600,
+            time % 3600 / 60,
+            time % 60
+        )
+    }
+}
+
+/// Días desde 1970-01-01 hasta la fecha dada, del calendario
+/// gregoriano (algoritmo `days_from_civil` de Howard Hinnant).
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let month = i64::from(if month > 2 { month - 3 } else { month + 9 });
+    let day_of_year = (153 * month + 2) / 5 + i64::from(day) - 1;
+    let day_of_era =
+        year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146097 + day_of_era - 719468
+}
+
+/// Inversa de `days_from_civil`: año, mes y día de `days`.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = if days >= 0 { days } else { days - 146096 } / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524
+        - day_of_era / 146096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month + 2) / 5 + 1) as u32;
+    let month = if month < 10 { month + 3 } else { month - 9 } as u32;
+    let year = year_of_era + era * 400;
+
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sun, 06 Nov 1994 08:49:37 GMT
+    const EXAMPLE: u64 = 784111777;
+
+    #[test]
+    fn test_format() {
+        let date = HttpDate { seconds: EXAMPLE };
+
+        assert_eq!(date.to_string(), "Sun, 06 Nov 1994 08:49:37 GMT");
+        assert_eq!(
+            HttpDate::from(UNIX_EPOCH).to_string(),
+            "Thu, 01 Jan 1970 00:00:00 GMT"
+        );
+        assert_eq!(
+            HttpDate { seconds: 951782400 }.to_string(),
+            "Tue, 29 Feb 2000 00:00:00 GMT"
+        );
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let formats = [
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ];
+
+        for value in formats {
+            let date = HttpDate::parse(value);
+
+            assert_eq!(date, Some(HttpDate { seconds: EXAMPLE }), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        let values = [
+            "",
+            "yesterday",
+            "Sun, 06 Nov 1994 08:49:37 UTC",
+            "Sun, 06 Foo 1994 08:49:37 GMT",
+            "Sun, 32 Nov 1994 08:49:37 GMT",
+            "Sun, 06 Nov 1994 24:00:00 GMT",
+            "Sun, 06 Nov 1994 08:49 GMT",
+            "Sun, 06 Nov 1969 08:49:37 GMT",
+        ];
+
+        for value in values {
+            assert_eq!(HttpDate::parse(value), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_system_time_round_trip() {
+        let time = UNIX_EPOCH + Duration::from_millis(EXAMPLE * 1000 + 999);
+        let date = HttpDate::from(time);
+
+        // Se pierden las fracciones de segundo
+        assert_eq!(date, HttpDate { seconds: EXAMPLE });
+        assert_eq!(HttpDate::parse(&date.to_string()), Some(date));
+        assert_eq!(
+            SystemTime::from(date),
+            UNIX_EPOCH + Duration::from_secs(EXAMPLE)
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::fmt;
+use std::fs::Metadata;
+use std::time::UNIX_EPOCH;
+
+use crate::http::ContentCoding;
+
+// ---- -- Entity Tag -- ---- \\
+
+/// Validador `ETag` de una representación (RFC 9110, sección 8.8.3).
+///
+/// Un ETag débil (`W/"..."`) solo indica que el contenido es
+/// equivalente, no idéntico byte a byte.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityTag {
+    weak: bool,
+    // Valor entre comillas, sin las comillas
+    tag: String,
+}
+
+impl EntityTag {
+    #[allow(dead_code)]
+    pub fn strong(tag: &str) -> EntityTag {
+        EntityTag {
+            weak: false,
+            tag: tag.to_string(),
+        }
+    }
+
+    /// ETag de un archivo, a partir de su tamaño, su fecha de
+    /// modificación y su inodo.
+    ///
+    /// Es fuerte aunque el archivo se acabe de escribir: las subidas
+    /// reemplazan el archivo con un rename, así cada versión tiene un
+    /// inodo distinto aunque la fecha no alcance a cambiar.
+    pub fn from_metadata(metadata: &Metadata) -> EntityTag {
+        let nanos = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map_or(0, |duration| duration.as_nanos());
+
+        let tag =
+            format!("{:x}-{:x}-{:x}", metadata.len(), nanos, inode(metadata));
+        EntityTag { weak: false, tag }
+    }
+
+    /// Parsea un ETag como `"xyz"` o `W/"xyz"`.
+    pub fn parse(value: &str) -> Option<EntityTag> {
+        let value = value.trim();
+        let (weak, quoted) = match value.strip_prefix("W/") {
+            Some(quoted) => (true, quoted),
+            None => (false, value),
+        };
+
+        let tag = quoted.strip_prefix('"')?.strip_suffix('"')?;
+
+        // Entre comillas se admite cualquier carácter visible salvo '"'
+        let valid = |c: char| c == '\x21' || ('\x23'..='\x7e').contains(&c);
+        if !tag.chars().all(|c| valid(c) || !c.is_ascii()) {
+            return None;
+        }
+
+        Some(EntityTag {
+            weak,
+            tag: tag.to_string(),
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn is_weak(&self) -> bool {
+        self.weak
+    }
+
+    /// ETag de la representación codificada con `coding`, por ejemplo
+    /// `"abc-gzip"` para `"abc"`.
+    ///
+    /// Sigue siendo fuerte: los bytes comprimidos son otra
+    /// representación y necesitan un validador propio.
+    pub fn with_coding(&self, coding: ContentCoding) -> EntityTag {
+        EntityTag {
+            weak: self.weak,
+            tag: format!("{}-{}", self.tag, coding.as_str()),
+        }
+    }
+
+    /// El ETag de la representación sin codificar, quitando el sufijo
+    /// que agrega `with_coding` si lo tiene.
+    pub fn without_coding(&self) -> EntityTag {
+        let base = self.tag.rsplit_once('-').and_then(|(base, suffix)| {
+            match ContentCoding::from_token(suffix) {
+                Some(ContentCoding::Identity) | None => None,
+                Some(_) => Some(base),
+            }
+        });
+
+        EntityTag {
+            weak: self.weak,
+            tag: base.unwrap_or(&self.tag).to_string(),
+        }
+    }
+
+    /// Comparación fuerte: ambos deben ser fuertes y con el mismo valor.
+    pub fn strong_eq(&self, other: &EntityTag) -> bool {
+        !self.weak && !other.weak && self.tag == other.tag
+    }
+
+    /// Comparación débil: el mismo valor, sin importar si son débiles.
+    pub fn weak_eq(&self, other: &EntityTag) -> bool {
+        self.tag == other.tag
+    }
+}
+
+impl fmt::Display for EntityTag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weak {
+            write!(f, "W/")?;
+        }
+
+        write!(f, "\"{}\"", self.tag)
+    }
+}
+
+#[cfg(unix)]
+fn inode(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.ino()
+}
+
+#[cfg(not(unix))]
+fn inode(_metadata: &Metadata) -> u64 {
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let strong = EntityTag::parse("\"abc-123\"").unwrap();
+        let weak = EntityTag::parse(" W/\"abc-123\" ").unwrap();
+
+        assert!(!strong.is_weak());
+        assert!(weak.is_weak());
+        assert_eq!(strong.to_string(), "\"abc-123\"");
+        assert_eq!(weak.to_string(), "W/\"abc-123\"");
+        assert_eq!(EntityTag::parse("\"\""), Some(EntityTag::strong("")));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        for value in ["abc", "\"abc", "w/\"abc\"", "\"a\"b\"", "\"a b\"", ""] {
+            assert_eq!(EntityTag::parse(value), None, "{}", value);
+        }
+    }
+
+    fn weak(tag: &str) -> EntityTag {
+        EntityTag::parse(&format!("W/\"{}\"", tag)).unwrap()
+    }
+
+    #[test]
+    fn test_strong_eq() {
+        let tag = EntityTag::strong("1");
+
+        assert!(tag.strong_eq(&EntityTag::strong("1")));
+        assert!(!tag.strong_eq(&EntityTag::strong("2")));
+        assert!(!tag.strong_eq(&weak("1")));
+        assert!(!weak("1").strong_eq(&weak("1")));
+    }
+
+    #[test]
+    fn test_weak_eq() {
+        let tag = EntityTag::strong("1");
+
+        assert!(tag.weak_eq(&EntityTag::strong("1")));
+        assert!(tag.weak_eq(&weak("1")));
+        assert!(weak("1").weak_eq(&weak("1")));
+        assert!(!tag.weak_eq(&EntityTag::strong("2")));
+    }
+
+    #[test]
+    fn test_coding() {
+        let tag = EntityTag::strong("3-a1");
+        let gzip = tag.with_coding(ContentCoding::Gzip);
+
+        assert_eq!(gzip.to_string(), "\"3-a1-gzip\"");
+        assert!(!gzip.is_weak());
+        assert!(!gzip.strong_eq(&tag));
+        assert!(gzip.without_coding().strong_eq(&tag));
+        assert_eq!(tag.without_coding(), tag);
+        assert_eq!(weak("a-deflate").without_coding(), weak("a"));
+    }
+
+    #[test]
+    fn test_from_metadata() {
+        let path = std::env::temp_dir()
+            .join(format!("entity_tag_test_{}", std::process::id()));
+        std::fs::write(&path, "abc").unwrap();
+
+        let metadata = std::fs::metadata(&path).unwrap();
+        let tag = EntityTag::from_metadata(&metadata);
+
+        std::fs::remove_file(&path).unwrap();
+
+        // Recién escrito sigue siendo fuerte, If-Match lo puede comparar
+        assert!(!tag.is_weak());
+        assert!(tag.to_string().starts_with("\"3-"));
+        assert_eq!(tag, EntityTag::from_metadata(&metadata));
+    }
+}
@@ -0,0 +1,261 @@
+// ---- -- Header Map -- ---- \\
+
+/// Cabeceras de una solicitud o respuesta HTTP.
+///
+/// Los nombres se comparan sin distinguir mayúsculas, un mismo nombre
+/// puede tener varios valores y la iteración respeta el orden de inserción.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeaderMap {
+    entries: Vec<(String, String)>,
+}
+
+impl HeaderMap {
+    pub fn new() -> Self {
+        HeaderMap::default()
+    }
+
+    /// Primer valor de la cabecera `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Todos los valores de la cabecera `name`, en orden de llegada.
+    pub fn get_all<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.entries
+            .iter()
+            .filter(move |(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn contains_key(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Agrega la cabecera reemplazando los valores que ya tuviera.
+    pub fn insert(&mut self, name: &str, value: &str) {
+        let position = self
+            .entries
+            .iter()
+            .position(|(key, _)| key.eq_ignore_ascii_case(name));
+
+        match position {
+            Some(position) => {
+                self.entries[position] = (name.to_string(), value.to_string());
+                self.remove_after(name, position);
+            }
+            None => self.append(name, value),
+        }
+    }
+
+    /// Agrega un valor más a la cabecera, conservando los anteriores.
+    pub fn append(&mut self, name: &str, value: &str) {
+        self.entries.push((name.to_string(), value.to_string()));
+    }
+
+    /// Elimina todos los valores de la cabecera, devolviendo el primero.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let value = self.get(name)?.to_string();
+
+        self.entries
+            .retain(|(key, _)| !key.eq_ignore_ascii_case(name));
+
+        Some(value)
+    }
+
+    fn remove_after(&mut self, name: &str, position: usize) {
+        let mut index = 0;
+
+        self.entries.retain(|(key, _)| {
+            index += 1;
+            index <= position + 1 || !key.eq_ignore_ascii_case(name)
+        });
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    /// Valores de una cabecera de lista (separada por comas),
+    /// combinando todas sus apariciones.
+    pub fn get_list<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        self.get_all(name)
+            .flat_map(|value| value.split(','))
+            .map(|item| item.trim())
+            .filter(|item| !item.is_empty())
+    }
+
+    // ---- -- Typed headers -- ---- \\
+
+    /// `Content-Length`, `None` si no está o no es un número.
+    pub fn content_length(&self) -> Option<usize> {
+        self.get("Content-Length")?.trim().parse().ok()
+    }
+
+    pub fn content_type(&self) -> Option<&str> {
+        self.get("Content-Type")
+    }
+
+    /// Codificaciones listadas en `Accept-Encoding` con su q-value,
+    /// 1 si no lo indican y 0 si no es un número válido.
+    pub fn accept_encoding(&self) -> Vec<(&str, f32)> {
+        self.quality_list("Accept-Encoding")
+    }
+
+    /// Tipos de contenido listados en `Accept` con su q-value, los
+    /// demás parámetros de cada tipo se descartan.
+    pub fn accept(&self) -> Vec<(&str, f32)> {
+        self.quality_list("Accept")
+    }
+
+    /// Valores de una cabecera de lista con q-values (`Accept`,
+    /// `Accept-Encoding`...).
+    fn quality_list<'a>(&'a self, name: &'a str) -> Vec<(&'a str, f32)> {
+        self.get_list(name)
+            .map(|item| {
+                let mut params = item.split(';').map(|param| param.trim());
+                let coding = params.next().unwrap_or("");
+
+                let qvalue = params
+                    .filter_map(|param| param.split_once('='))
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case("q"))
+                    .map_or(1.0, |(_, value)| {
+                        value.trim().parse::<f32>().unwrap_or(0.0)
+                    });
+
+                (coding, qvalue.clamp(0.0, 1.0))
+            })
+            .collect()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for HeaderMap {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let entries = iter
+            .into_iter()
+            .map(|(key, value)| (key.into(), value.into()))
+            .collect();
+
+        HeaderMap { entries }
+    }
+}
+
+impl<K: Into<String>, V: Into<String>, const N: usize> From<[(K, V); N]>
+    for HeaderMap
+{
+    fn from(entries: [(K, V); N]) -> Self {
+        entries.into_iter().collect()
+    }
+}
+
+impl IntoIterator for HeaderMap {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_case_insensitive() {
+        let headers = HeaderMap::from([("User-Agent", "curl/7.64.1")]);
+
+        assert_eq!(headers.get("user-agent"), Some("curl/7.64.1"));
+        assert_eq!(headers.get("USER-AGENT"), Some("curl/7.64.1"));
+        assert!(headers.contains_key("User-agent"));
+        assert_eq!(headers.get("Accept"), None);
+    }
+
+    #[test]
+    fn test_append_keeps_multiple_values() {
+        let mut headers = HeaderMap::new();
+        headers.append("Cookie", "a=1");
+        headers.append("cookie", "b=2");
+
+        let cookies: Vec<&str> = headers.get_all("Cookie").collect();
+
+        assert_eq!(cookies, ["a=1", "b=2"]);
+        assert_eq!(headers.get("Cookie"), Some("a=1"));
+    }
+
+    #[test]
+    fn test_insert_replaces_all_values() {
+        let mut headers = HeaderMap::new();
+        headers.append("Accept", "text/html");
+        headers.append("Host", "localhost");
+        headers.append("accept", "text/plain");
+
+        headers.insert("ACCEPT", "*/*");
+
+        let entries: Vec<(&str, &str)> = headers.iter().collect();
+        assert_eq!(entries, [("ACCEPT", "*/*"), ("Host", "localhost")]);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut headers = HeaderMap::new();
+        headers.append("Accept", "text/html");
+        headers.append("Host", "localhost");
+        headers.append("accept", "text/plain");
+
+        headers.append("ACCEPT", "*/*");
+
+        assert_eq!(headers.remove("accept"), Some("text/html".to_string()));
+        assert_eq!(headers.remove("accept"), None);
+        assert_eq!(headers.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_iter_insertion_order() {
+        let headers =
+            HeaderMap::from([("Host", "a"), ("Accept", "b"), ("Cookie", "c")]);
+
+        let names: Vec<&str> = headers.iter().map(|(key, _)| key).collect();
+
+        assert_eq!(names, ["Host", "Accept", "Cookie"]);
+    }
+
+    #[test]
+    fn test_typed_headers() {
+        let headers = HeaderMap::from([
+            ("content-length", " 35 "),
+            ("Content-Type", "text/plain"),
+            ("Accept-Encoding", "gzip;q=0.5, deflate"),
+            ("accept-encoding", "br; Q=0, *;q=x"),
+            ("Accept", "text/html;level=1;q=0.8, application/json"),
+        ]);
+
+        assert_eq!(headers.content_length(), Some(35));
+        assert_eq!(headers.content_type(), Some("text/plain"));
+        assert_eq!(
+            headers.accept_encoding(),
+            [("gzip", 0.5), ("deflate", 1.0), ("br", 0.0), ("*", 0.0)]
+        );
+        assert_eq!(
+            headers.accept(),
+            [("text/html", 0.8), ("application/json", 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_content_length_invalid() {
+        let headers = HeaderMap::from([("Content-Length", "abc")]);
+
+        assert_eq!(headers.content_length(), None);
+    }
+}
@@ -1,7 +1,11 @@
 mod parser;
 pub use parser::parse_request;
 
-pub const SUPPORTED_ENCODEING: [&str; 1] = ["gzip"];
+mod header_map;
+pub use header_map::HeaderMap;
+
+mod percent_encoding;
+pub use percent_encoding::{percent_decode, percent_encode_path};
 
 mod uri_path;
 pub use uri_path::UriPath;
@@ -9,5 +13,29 @@ pub use uri_path::UriPath;
 mod request;
 pub use request::{HttpMethod, HttpRequest, HttpVersion, RequestLine};
 
+mod reader;
+pub use reader::RequestReader;
+
+mod body;
+pub use body::Body;
+
+mod content_coding;
+pub use content_coding::ContentCoding;
+
+mod date;
+pub use date::HttpDate;
+
+mod entity_tag;
+pub use entity_tag::EntityTag;
+
+mod conditional;
+pub use conditional::{evaluate_preconditions, Precondition, Validators};
+
+mod range;
+pub use range::{parse_range, ByteRange};
+
+mod status_code;
+pub use status_code::StatusCode;
+
 mod response;
 pub use response::HttpResponse;
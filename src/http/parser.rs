@@ -1,16 +1,29 @@
+pub use crate::http::HeaderMap;
 pub use crate::http::UriPath;
 pub use crate::http::{HttpMethod, HttpRequest, HttpVersion, RequestLine};
 
+use std::collections::HashMap;
+use std::str::from_utf8;
+
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until, take_while1},
-    character::is_alphanumeric,
-    combinator::opt,
+    bytes::complete::{tag, take, take_until, take_while, take_while1},
+    bytes::streaming,
+    combinator::{map_res, opt},
+    error::{Error, ErrorKind},
     multi::many0,
     IResult, Parser,
 };
 
-use std::collections::HashMap;
+use crate::errors::Errors;
+
+// ---- -- Tokens -- ---- \\
+
+/// Indica si `c` puede formar parte de un token (RFC 9110, "tchar"),
+/// como los nombres de métodos y de cabeceras.
+pub fn is_token_char(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&c)
+}
 
 // ---- -- HTTP Version -- ---- \\
 
@@ -20,9 +33,9 @@ fn http_version(input: &[u8]) -> IResult<&[u8], HttpVersion> {
     let is_version = |c: u8| c.is_ascii_digit() || c == b'.';
 
     let (input, _) = tag("HTTP/")(input)?;
-    let (input, version) = take_while1(is_version).parse(input)?;
+    let (input, version) =
+        map_res(take_while1(is_version), from_utf8).parse(input)?;
 
-    let version = std::str::from_utf8(version).unwrap();
     let version = HttpVersion::from(version);
 
     Ok((input, version))
@@ -31,10 +44,9 @@ fn http_version(input: &[u8]) -> IResult<&[u8], HttpVersion> {
 // ---- -- HTTP Method -- ---- \\
 
 fn http_method(input: &[u8]) -> IResult<&[u8], HttpMethod> {
-    let mut method = take_while1(is_alphanumeric);
+    let mut method = map_res(take_while1(is_token_char), from_utf8);
 
     let (input, method) = method.parse(input)?;
-    let method = std::str::from_utf8(method).unwrap();
     let method = HttpMethod::from(method);
 
     Ok((input, method))
@@ -43,19 +55,21 @@ fn http_method(input: &[u8]) -> IResult<&[u8], HttpMethod> {
 // ---- -- URI path -- ---- \\
 
 /// Parsea la ruta URI desde la entrada proporcionada.
+///
+/// Falla si la ruta contiene espacios en blanco, caracteres de control,
+/// bytes que no son UTF-8 o si `UriPath` la rechaza al decodificarla.
 fn http_uri(input: &[u8]) -> IResult<&[u8], UriPath> {
-    let mut is_not_space = take_while1(|c| c != b' ');
+    let is_uri_char = |c: u8| c.is_ascii_graphic() || c >= 0x80;
+    let uri = map_res(take_while1(is_uri_char), from_utf8);
 
-    let (input, uri) = is_not_space.parse(input)?;
-    let uri = std::str::from_utf8(uri).unwrap();
-    let uri = UriPath::new(uri);
+    let (input, uri) = map_res(uri, UriPath::new).parse(input)?;
 
     Ok((input, uri))
 }
 
 // ---- -- HTTP Request line -- ---- \\
 
-fn request_line(input: &[u8]) -> IResult<&[u8], RequestLine> {
+pub fn request_line(input: &[u8]) -> IResult<&[u8], RequestLine> {
     let mut space = take_while1(|c| c == b' ');
     let line_ending = alt((tag("\r\n"), tag("\n")));
 
@@ -80,19 +94,22 @@ fn request_line(input: &[u8]) -> IResult<&[u8], RequestLine> {
 // ---- -- HTTP Headers -- ---- \\
 
 fn header(input: &[u8]) -> IResult<&[u8], (String, String)> {
-    let mut is_key = take_while1(|c| c != b':');
-    let mut is_token = take_while1(|c| c != b'\r' && c != b'\n');
+    let mut is_key = map_res(take_while1(is_token_char), from_utf8);
+    let mut whitespace = take_while(|c| c == b' ' || c == b'\t');
+    let mut is_value = take_while(|c| c != b'\r' && c != b'\n');
     let line_ending = alt((tag("\r\n"), tag("\n")));
 
     let (input, key) = is_key.parse(input)?;
-    let (input, _) = tag(": ")(input)?;
-    let (input, value) = is_token.parse(input)?;
+    let (input, _) = tag(":")(input)?;
+    let (input, _) = whitespace.parse(input)?;
+    let (input, value) = is_value.parse(input)?;
     let (input, _) = opt(line_ending)(input)?;
 
-    let key = std::str::from_utf8(key).unwrap();
-    let value = std::str::from_utf8(value).unwrap();
+    // Los valores pueden traer bytes fuera de ASCII (obs-text),
+    // se conservan en lugar de rechazar la solicitud
+    let value = String::from_utf8_lossy(value);
 
-    let result = (key.to_string(), value.to_string());
+    let result = (key.to_string(), value.trim_end().to_string());
     Ok((input, result))
 }
 
@@ -102,71 +119,180 @@ fn header(input: &[u8]) -> IResult<&[u8], (String, String)> {
 /// incluyendo la línea de solicitud y los headers.
 fn parse_request_metadata(
     input: &[u8],
-) -> IResult<&[u8], (RequestLine, HashMap<String, String>)> {
-    let mut headers_hash: HashMap<String, String> = HashMap::new();
-
+) -> IResult<&[u8], (RequestLine, HeaderMap)> {
     let (input, request_line) = request_line(input)?;
     let (input, headers) = opt(many0(header))(input)?;
 
-    if let Some(headers) = headers {
-        headers.iter().for_each(|(key, value)| {
-            headers_hash.insert(key.clone(), value.clone());
-        });
-    }
+    // Las cabeceras repetidas se conservan todas, en orden de llegada
+    let headers: HeaderMap = headers.unwrap_or_default().into_iter().collect();
 
-    let result = (request_line, headers_hash);
+    let result = (request_line, headers);
     Ok((input, result))
 }
 
+// ---- -- Chunked body -- ---- \\
+
+// Los parsers de esta sección son "streaming": devuelven
+// `nom::Err::Incomplete` cuando todavía faltan bytes, lo que permite
+// usarlos tanto para saber si el body llegó completo como para decodificarlo.
+
+/// Parsea la línea de tamaño de un chunk: "<hex>[;extensiones]\r\n".
+///
+/// Las extensiones del chunk se aceptan pero se descartan.
+pub fn chunk_size(input: &[u8]) -> IResult<&[u8], usize> {
+    let (input, size) =
+        streaming::take_while1(|c: u8| c.is_ascii_hexdigit())(input)?;
+    let (input, extensions) = streaming::take_until("\r\n")(input)?;
+    let (input, _) = streaming::tag("\r\n")(input)?;
+
+    let extension_start = extensions.iter().find(|c| !c.is_ascii_whitespace());
+    if extension_start.is_some_and(|c| *c != b';') {
+        return Err(nom::Err::Failure(Error::new(input, ErrorKind::Char)));
+    }
+
+    // Los dígitos ya fueron validados, solo puede fallar por overflow
+    let size = std::str::from_utf8(size).unwrap_or_default();
+    let size = usize::from_str_radix(size, 16).map_err(|_| {
+        nom::Err::Failure(Error::new(input, ErrorKind::TooLarge))
+    })?;
+
+    Ok((input, size))
+}
+
+/// Parsea un chunk completo y devuelve sus datos.
+///
+/// El último chunk (tamaño 0) devuelve datos vacíos y no consume el
+/// "\r\n" final, que pertenece a la sección de trailers.
+pub fn chunk(input: &[u8]) -> IResult<&[u8], &[u8]> {
+    let (input, size) = chunk_size(input)?;
+
+    if size == 0 {
+        return Ok((input, &input[..0]));
+    }
+
+    let (input, data) = streaming::take(size)(input)?;
+    let (input, _) = streaming::tag("\r\n")(input)?;
+
+    Ok((input, data))
+}
+
+/// Parsea los trailers que siguen al último chunk, hasta la línea vacía.
+pub fn trailers(input: &[u8]) -> IResult<&[u8], Vec<(String, String)>> {
+    let mut trailers = Vec::new();
+    let mut input = input;
+
+    loop {
+        match streaming::tag::<_, _, Error<&[u8]>>("\r\n")(input) {
+            Ok((input, _)) => return Ok((input, trailers)),
+            Err(nom::Err::Error(_)) => {}
+            Err(e) => return Err(e),
+        }
+
+        let (rest, line) = streaming::take_until("\r\n")(input)?;
+        let (rest, _) = streaming::tag("\r\n")(rest)?;
+
+        let line = String::from_utf8_lossy(line);
+        let Some((key, value)) = line.split_once(':') else {
+            return Err(nom::Err::Failure(Error::new(input, ErrorKind::Char)));
+        };
+
+        trailers.push((key.trim().to_string(), value.trim().to_string()));
+        input = rest;
+    }
+}
+
+// Datos decodificados y trailers de un body en chunks.
+type ChunkedBody = (Vec<u8>, HeaderMap);
+
+/// Decodifica un body con `Transfer-Encoding: chunked`,
+/// devolviendo los datos y los trailers.
+fn chunked_body(input: &[u8]) -> IResult<&[u8], ChunkedBody> {
+    let mut body = Vec::new();
+    let mut input = input;
+
+    loop {
+        let (rest, data) = chunk(input)?;
+        input = rest;
+
+        if data.is_empty() {
+            break;
+        }
+
+        body.extend_from_slice(data);
+    }
+
+    let (input, trailers) = trailers(input)?;
+    let trailers = trailers.into_iter().collect();
+
+    Ok((input, (body, trailers)))
+}
+
+/// Indica si el body de la solicitud viene codificado en chunks.
+fn is_chunked(headers: &HeaderMap) -> bool {
+    headers
+        .get_list("Transfer-Encoding")
+        .last()
+        .is_some_and(|coding| coding.eq_ignore_ascii_case("chunked"))
+}
+
 // ---- -- Parse Request -- ---- \\
 
 /// Parsea la solicitud HTTP completa, incluyendo
 /// la línea de solicitud, los headers y el cuerpo.
+///
+/// Solo consume el body indicado por `Content-Length` (o por los chunks).
 fn request(input: &[u8]) -> IResult<&[u8], HttpRequest> {
     // Encuentra la posición del patrón '\r\n\r\n',
     // en el input para separar request y body.
     let line_blank = "\r\n\r\n";
 
     let (input, request) = take_until(line_blank)(input)?;
-    let (_, metadata) = parse_request_metadata(request)?;
+    let (rest, metadata) = parse_request_metadata(request)?;
+
+    // Una línea de cabecera que no se pudo parsear invalida la solicitud
+    if !rest.is_empty() {
+        return Err(nom::Err::Failure(Error::new(rest, ErrorKind::Verify)));
+    }
+
     let (request_line, headers) = metadata;
     let (input, _) = opt(tag(line_blank))(input)?;
-    let (mut input, _) = opt(tag("\r\n"))(input)?;
 
-    let body = if input.is_empty() {
-        None
+    let (input, (body, trailers)) = if is_chunked(&headers) {
+        chunked_body(input)?
     } else {
-        Some(input.to_vec())
+        let length = headers.content_length().unwrap_or(0);
+        let (input, body) = take(length)(input)?;
+
+        (input, (body.to_vec(), HeaderMap::new()))
     };
 
-    if body.is_some() {
-        input = &b""[..]; // Limpiar el input
-    }
+    let body = if body.is_empty() { None } else { Some(body) };
 
     let result = HttpRequest {
         request_line,
         headers,
         body,
+        trailers,
+        params: HashMap::new(),
     };
 
     Ok((input, result))
 }
 
-pub fn parse_request(input: &[u8]) -> anyhow::Result<HttpRequest> {
-    let parse_result = request(input);
-
-    parse_result
-        .map(|(_, input)| input)
-        .map_err(|err| match err {
-            nom::Err::Incomplete(needed) => {
-                let message = format!("Incomplete input: {:?}", needed);
-                anyhow::anyhow!(message)
-            }
-            nom::Err::Error(e) | nom::Err::Failure(e) => {
-                let message = format!("Failed to parse input: {:?}", e);
-                anyhow::anyhow!(message)
-            }
-        })
+/// Parsea una solicitud completa.
+///
+/// `RequestReader` ya separó la solicitud de las siguientes del pipeline
+/// (y de las líneas vacías entre ellas), así que `input` debe contener
+/// exactamente una solicitud, si sobran bytes la solicitud no es válida.
+pub fn parse_request(input: &[u8]) -> Result<HttpRequest, Errors> {
+    match request(input) {
+        Ok((&[], request)) => Ok(request),
+        Ok(_) => Err(Errors::MalformedRequest),
+        Err(nom::Err::Incomplete(_)) => Err(Errors::IncompleteRequest),
+        Err(nom::Err::Error(_) | nom::Err::Failure(_)) => {
+            Err(Errors::MalformedRequest)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -204,7 +330,7 @@ mod tests {
     #[test]
     fn test_http_uri_parsing() {
         let input: &[u8] = b"/data/test.html";
-        let expected_uri = UriPath::new("/data/test.html");
+        let expected_uri = UriPath::new("/data/test.html").unwrap();
 
         let (input, uri) = http_uri(input).unwrap();
         assert_eq!(expected_uri, uri);
@@ -221,7 +347,7 @@ mod tests {
 
         let expected_request_line = RequestLine {
             method: HttpMethod::Get,
-            uri: UriPath::new("/data/test.html"),
+            uri: UriPath::new("/data/test.html").unwrap(),
             http_version: HttpVersion::V1_1,
         };
 
@@ -238,7 +364,7 @@ mod tests {
 
         let expected_request_line = RequestLine {
             method: HttpMethod::Get,
-            uri: UriPath::new("/data/test.html"),
+            uri: UriPath::new("/data/test.html").unwrap(),
             http_version: HttpVersion::V1_1,
         };
 
@@ -255,7 +381,7 @@ mod tests {
 
         let expected_request_line = RequestLine {
             method: HttpMethod::Post,
-            uri: UriPath::new("/files/test.html"),
+            uri: UriPath::new("/files/test.html").unwrap(),
             http_version: HttpVersion::V1_1,
         };
 
@@ -282,21 +408,33 @@ mod tests {
         assert_eq!(input, expected_input);
     }
 
+    #[test]
+    fn test_http_header_without_space_and_empty_value() {
+        let input: &[u8] = b"Host:localhost\r\nX-Empty: \r\n";
+
+        let (input, host) = header(input).unwrap();
+        let (input, empty) = header(input).unwrap();
+
+        assert_eq!(host, ("Host".to_string(), "localhost".to_string()));
+        assert_eq!(empty, ("X-Empty".to_string(), "".to_string()));
+        assert_eq!(input, b"");
+    }
+
     // ---- -- Parse HTTP Request metadata -- ---- \\
 
     #[test]
     fn test_request_metadata_parsing() {
         let expected_request_line = RequestLine {
             method: HttpMethod::Get,
-            uri: UriPath::new("/data/test.html"),
+            uri: UriPath::new("/data/test.html").unwrap(),
             http_version: HttpVersion::V1_1,
         };
-        let expected_headers = HashMap::from([
+        let expected_headers = HeaderMap::from([
+            ("Host".to_string(), "www.test101.com".to_string()),
             (
                 "Accept".to_string(),
                 "image/gif, image/jpeg, *//*".to_string(),
             ),
-            ("Host".to_string(), "www.test101.com".to_string()),
             ("Accept-Language".to_string(), "en-us".to_string()),
             ("Accept-Encoding".to_string(), "gzip, deflate".to_string()),
             ("User-Agent".to_string(), "Mozilla/4.0".to_string()),
@@ -333,10 +471,10 @@ mod tests {
         let expected_request = HttpRequest {
             request_line: RequestLine {
                 method: HttpMethod::Get,
-                uri: UriPath::new("/data/test.html"),
+                uri: UriPath::new("/data/test.html").unwrap(),
                 http_version: HttpVersion::V1_1,
             },
-            headers: HashMap::from([
+            headers: HeaderMap::from([
                 ("Host".to_string(), "www.test101.com".to_string()),
                 (
                     "Accept".to_string(),
@@ -344,10 +482,12 @@ mod tests {
                 ),
                 ("Accept-Language".to_string(), "en-us".to_string()),
                 ("Accept-Encoding".to_string(), "gzip, deflate".to_string()),
-                ("Content-Length".to_string(), "35".to_string()),
+                ("Content-Length".to_string(), "31".to_string()),
                 ("User-Agent".to_string(), "Mozilla/4.0".to_string()),
             ]),
             body: Some("bookId=12345&author=Tan+Ah+Teck".as_bytes().to_vec()),
+            trailers: HeaderMap::new(),
+            params: HashMap::new(),
         };
 
         let request_lines = [
@@ -356,7 +496,7 @@ mod tests {
             "Accept: image/gif, image/jpeg, *//*",
             "Accept-Language: en-us",
             "Accept-Encoding: gzip, deflate",
-            "Content-Length: 35",
+            "Content-Length: 31",
             "User-Agent: Mozilla/4.0",
             "",
             "bookId=12345&author=Tan+Ah+Teck",
@@ -375,10 +515,10 @@ mod tests {
         let expected_request = HttpRequest {
             request_line: RequestLine {
                 method: HttpMethod::Post,
-                uri: UriPath::new("/data/test.html"),
+                uri: UriPath::new("/data/test.html").unwrap(),
                 http_version: HttpVersion::V1_1,
             },
-            headers: HashMap::from([
+            headers: HeaderMap::from([
                 ("Host".to_string(), "www.test101.com".to_string()),
                 ("Accept-Language".to_string(), "en-us".to_string()),
                 ("Accept-Encoding".to_string(), "gzip, deflate".to_string()),
@@ -386,6 +526,8 @@ mod tests {
                 ("Content-Length".to_string(), "0".to_string()),
             ]),
             body: None,
+            trailers: HeaderMap::new(),
+            params: HashMap::new(),
         };
 
         let request_lines = [
@@ -403,7 +545,8 @@ mod tests {
         let (input, request) = request(plain_request.as_bytes()).unwrap();
         assert_eq!(expected_request, request);
 
-        let expected_input = b"";
+        // La línea vacía sobrante la descarta `RequestReader`
+        let expected_input = b"\r\n";
         assert_eq!(input, expected_input);
     }
 
@@ -412,11 +555,13 @@ mod tests {
         let expected_request = HttpRequest {
             request_line: RequestLine {
                 method: HttpMethod::Get,
-                uri: UriPath::new(""),
+                uri: UriPath::new("").unwrap(),
                 http_version: HttpVersion::V1_1,
             },
-            headers: HashMap::new(),
+            headers: HeaderMap::new(),
             body: None,
+            trailers: HeaderMap::new(),
+            params: HashMap::new(),
         };
 
         let request_lines = ["GET / HTTP/1.1", "\r\n", ""];
@@ -425,7 +570,151 @@ mod tests {
         let (input, request) = request(plain_request.as_bytes()).unwrap();
         assert_eq!(expected_request, request);
 
+        // La línea vacía sobrante la descarta `RequestReader`
+        let expected_input = b"\r\n";
+        assert_eq!(input, expected_input);
+    }
+
+    // ---- -- Chunked body -- ---- \\
+
+    #[test]
+    fn test_chunk_size_with_extensions() {
+        let input: &[u8] = b"1A;name=value;flag\r\n";
+
+        let (input, size) = chunk_size(input).unwrap();
+
+        assert_eq!(size, 26);
+        assert_eq!(input, b"");
+    }
+
+    #[test]
+    fn test_chunk_incomplete() {
+        let input: &[u8] = b"5\r\nabc";
+
+        let err = chunk(input).unwrap_err();
+
+        assert!(matches!(err, nom::Err::Incomplete(_)));
+    }
+
+    #[test]
+    fn test_chunk_invalid_size() {
+        let input: &[u8] = b"zz\r\nabc\r\n";
+
+        let err = chunk(input).unwrap_err();
+
+        assert!(matches!(err, nom::Err::Error(_)));
+    }
+
+    #[test]
+    fn test_chunked_body_with_trailers() {
+        let input: &[u8] =
+            b"4\r\nWiki\r\n7;ext=1\r\npedia i\r\n0\r\nExpires: never\r\n\r\n";
+        let expected_trailers =
+            HeaderMap::from([("Expires".to_string(), "never".to_string())]);
+
+        let (input, (body, trailers)) = chunked_body(input).unwrap();
+
+        assert_eq!(body, b"Wikipedia i");
+        assert_eq!(trailers, expected_trailers);
+        assert_eq!(input, b"");
+    }
+
+    #[test]
+    fn test_complex_request_with_chunked_body_parsing() {
+        let request_lines = [
+            "POST /files/test.txt HTTP/1.1",
+            "Host: www.test101.com",
+            "Transfer-Encoding: chunked",
+            "",
+            "5",
+            "Hello",
+            "8",
+            ", World!",
+            "0",
+            "Checksum: abc",
+            "",
+            "",
+        ];
+        let plain_request: String = request_lines.join("\r\n");
+
+        let (input, request) = request(plain_request.as_bytes()).unwrap();
+
+        assert_eq!(request.body, Some(b"Hello, World!".to_vec()));
+        assert_eq!(
+            request.trailers,
+            HeaderMap::from([("Checksum".to_string(), "abc".to_string())])
+        );
+
         let expected_input = b"";
         assert_eq!(input, expected_input);
     }
+
+    // ---- -- Parse Request -- ---- \\
+
+    #[test]
+    fn test_parse_request_single_request() {
+        let plain_request =
+            "POST /files/a HTTP/1.1\r\nContent-Length: 5\r\n\r\nHello";
+
+        let request = parse_request(plain_request.as_bytes()).unwrap();
+
+        assert_eq!(request.uri().to_string(), "/files/a");
+        assert_eq!(request.body, Some(b"Hello".to_vec()));
+    }
+
+    #[test]
+    fn test_parse_request_rejects_trailing_bytes() {
+        // El pipeline lo separa `RequestReader`, el parser recibe una
+        // sola solicitud
+        let inputs = [
+            "POST /files/a HTTP/1.1\r\nContent-Length: 5\r\n\r\nHello!",
+            "GET /echo/abc HTTP/1.1\r\n\r\nGET / HTTP/1.1\r\n\r\n",
+            "\r\nGET / HTTP/1.1\r\n\r\n",
+        ];
+
+        for input in inputs {
+            let err = parse_request(input.as_bytes()).unwrap_err();
+
+            assert!(matches!(err, Errors::MalformedRequest), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_request_incomplete_body() {
+        let plain_request = "POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc";
+
+        let result = parse_request(plain_request.as_bytes());
+
+        assert!(result.is_err());
+    }
+
+    // ---- -- Malformed input -- ---- \\
+
+    #[test]
+    fn test_parse_request_uri_not_utf8() {
+        let plain_request = b"GET /\xff\xfe HTTP/1.1\r\n\r\n";
+
+        let err = parse_request(plain_request).unwrap_err();
+
+        assert!(matches!(err, Errors::MalformedRequest));
+    }
+
+    #[test]
+    fn test_parse_request_invalid_header_line() {
+        let plain_request = b"GET / HTTP/1.1\r\nHost localhost\r\n\r\n";
+
+        let err = parse_request(plain_request).unwrap_err();
+
+        assert!(matches!(err, Errors::MalformedRequest));
+    }
+
+    #[test]
+    fn test_parse_request_binary_garbage() {
+        let plain_request =
+            b"\x16\x03\x01\x02\x00\x01\x00\x01\xfc\x03\x03\r\n\r\n";
+
+        let err = parse_request(plain_request).unwrap_err();
+
+        assert!(matches!(err, Errors::MalformedRequest));
+    }
 }
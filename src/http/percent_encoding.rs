@@ -0,0 +1,128 @@
+// ---- -- Percent Encoding -- ---- \\
+
+/// Decodifica las secuencias "%XX" de `input`.
+///
+/// Las secuencias incompletas o con dígitos inválidos se conservan tal
+/// cual.
+pub fn percent_decode(input: &str) -> Vec<u8> {
+    let bytes = input.as_bytes();
+    let mut result = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let byte = match bytes[index] {
+            b'%' => match decode_hex_pair(&bytes[index + 1..]) {
+                Some(decoded) => {
+                    index += 2;
+                    decoded
+                }
+                None => b'%',
+            },
+            byte => byte,
+        };
+
+        result.push(byte);
+        index += 1;
+    }
+
+    result
+}
+
+/// Decodifica solo las secuencias "%XX" de caracteres no reservados
+/// (letras, dígitos, "-", ".", "_" y "~").
+///
+/// Codificados o no, esos caracteres significan lo mismo (RFC 3986,
+/// sección 6.2.2.2), el resto de las secuencias se conserva tal cual.
+pub fn decode_unreserved(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(position) = rest.find('%') {
+        result.push_str(&rest[..position]);
+        rest = &rest[position..];
+
+        match decode_hex_pair(&rest.as_bytes()[1..]) {
+            Some(byte) if is_unreserved(byte) => {
+                result.push(byte as char);
+                rest = &rest[3..];
+            }
+            _ => {
+                result.push('%');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Codifica los caracteres de `path` que no pueden aparecer tal cual
+/// en la ruta de una URI, conservando las "/".
+pub fn percent_encode_path(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+
+    for byte in path.bytes() {
+        if is_path_char(byte) {
+            result.push(byte as char);
+        } else {
+            result.push_str(&format!("%{:02X}", byte));
+        }
+    }
+
+    result
+}
+
+// unreserved, sub-delims, ":", "@" y "/" (RFC 3986, sección 3.3).
+fn is_path_char(c: u8) -> bool {
+    is_unreserved(c) || b"!$&'()*+,;=:@/".contains(&c)
+}
+
+// Caracteres no reservados (RFC 3986, sección 2.3).
+fn is_unreserved(c: u8) -> bool {
+    c.is_ascii_alphanumeric() || b"-._~".contains(&c)
+}
+
+fn decode_hex_pair(input: &[u8]) -> Option<u8> {
+    let pair = input.get(..2)?;
+    let pair = std::str::from_utf8(pair).ok()?;
+
+    if !pair.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    u8::from_str_radix(pair, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("a%20b%2Fc"), b"a b/c");
+        assert_eq!(percent_decode("%e2%9c%93"), "✓".as_bytes());
+    }
+
+    #[test]
+    fn test_percent_decode_invalid_sequences() {
+        assert_eq!(percent_decode("100%"), b"100%");
+        assert_eq!(percent_decode("%zz%4"), b"%zz%4");
+        assert_eq!(percent_decode("%+1"), b"%+1");
+    }
+
+    #[test]
+    fn test_decode_unreserved() {
+        assert_eq!(decode_unreserved("%2e%2E"), "..");
+        assert_eq!(decode_unreserved("%7Euser%2Dx"), "~user-x");
+        assert_eq!(decode_unreserved("a%2Fb%20c%25"), "a%2Fb%20c%25");
+        assert_eq!(decode_unreserved("ñ%6e%"), "ñn%");
+    }
+
+    #[test]
+    fn test_percent_encode_path() {
+        assert_eq!(percent_encode_path("/a b/c%d?"), "/a%20b/c%25d%3F");
+        assert_eq!(percent_encode_path("/ñ"), "/%C3%B1");
+        assert_eq!(percent_encode_path("/echo/Coo-donkey"), "/echo/Coo-donkey");
+    }
+}
@@ -0,0 +1,211 @@
+// Cantidad máxima de rangos por solicitud, con más se ignora la
+// cabecera y se responde el recurso completo.
+pub const MAX_RANGES: usize = 32;
+
+// ---- -- Byte Range -- ---- \\
+
+/// Rango de bytes de un recurso, con ambos extremos incluidos.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+impl ByteRange {
+    pub fn length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Valor de `Content-Range` para un recurso de `length` bytes.
+    pub fn content_range(&self, length: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, length)
+    }
+}
+
+/// Parsea la cabecera `Range` para un recurso de `length` bytes
+/// (RFC 9110, sección 14.1.2).
+///
+/// Devuelve `None` si la cabecera no es válida, no usa la unidad
+/// "bytes" o pide demasiados rangos: en ese caso se ignora y se
+/// responde el recurso completo. Los rangos que empiezan después del
+/// final del recurso se descartan, si no queda ninguno el resultado
+/// está vacío (416).
+///
+/// Los rangos que se superponen o son contiguos se unen en uno solo y
+/// el resultado queda ordenado, así cada byte se envía una sola vez y
+/// el archivo se puede leer de principio a fin.
+pub fn parse_range(value: &str, length: u64) -> Option<Vec<ByteRange>> {
+    let (unit, ranges) = value.trim().split_once('=')?;
+
+    if !unit.trim().eq_ignore_ascii_case("bytes") {
+        return None;
+    }
+
+    let specs: Vec<&str> = ranges
+        .split(',')
+        .map(|spec| spec.trim())
+        .filter(|spec| !spec.is_empty())
+        .collect();
+
+    if specs.is_empty() || specs.len() > MAX_RANGES {
+        return None;
+    }
+
+    let mut result = Vec::new();
+
+    for spec in specs {
+        let (first, last) = spec.split_once('-')?;
+        let first = parse_position(first)?;
+        let last = parse_position(last)?;
+
+        let range = match (first, last) {
+            // "-500": los últimos 500 bytes
+            (None, Some(suffix)) => {
+                if suffix == 0 || length == 0 {
+                    continue;
+                }
+
+                ByteRange {
+                    start: length.saturating_sub(suffix),
+                    end: length - 1,
+                }
+            }
+            // "500-" y "500-999"
+            (Some(start), last) => {
+                if last.is_some_and(|last| last < start) {
+                    return None;
+                }
+                if start >= length {
+                    continue;
+                }
+
+                let end = last.map_or(length - 1, |last| last.min(length - 1));
+                ByteRange { start, end }
+            }
+            (None, None) => return None,
+        };
+
+        result.push(range);
+    }
+
+    Some(coalesce(result))
+}
+
+/// Ordena los rangos y une los que se superponen o son contiguos.
+fn coalesce(mut ranges: Vec<ByteRange>) -> Vec<ByteRange> {
+    ranges.sort_by_key(|range| range.start);
+
+    let mut result: Vec<ByteRange> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match result.last_mut() {
+            Some(last) if range.start <= last.end.saturating_add(1) => {
+                last.end = last.end.max(range.end);
+            }
+            _ => result.push(range),
+        }
+    }
+
+    result
+}
+
+/// Parsea una posición de un rango, que puede estar vacía. Solo se
+/// aceptan dígitos (sin signo ni espacios internos).
+fn parse_position(value: &str) -> Option<Option<u64>> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return Some(None);
+    }
+    if !value.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    // Un número mayor a u64 solo puede estar fuera del recurso
+    Some(Some(value.parse().unwrap_or(u64::MAX)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u64, end: u64) -> ByteRange {
+        ByteRange { start, end }
+    }
+
+    #[test]
+    fn test_parse_range() {
+        let cases = [
+            ("bytes=0-499", vec![range(0, 499)]),
+            ("bytes=500-", vec![range(500, 999)]),
+            ("bytes=-200", vec![range(800, 999)]),
+            ("bytes=-5000", vec![range(0, 999)]),
+            ("bytes=900-5000", vec![range(900, 999)]),
+            ("Bytes = 0-0, -1", vec![range(0, 0), range(999, 999)]),
+            ("bytes=0-9,,20-29", vec![range(0, 9), range(20, 29)]),
+            ("bytes=1000-, 0-9", vec![range(0, 9)]),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(parse_range(value, 1000), Some(expected), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_parse_range_coalesce() {
+        let repeated = format!("bytes={}", vec!["0-"; MAX_RANGES].join(","));
+
+        let cases = [
+            (repeated.as_str(), vec![range(0, 999)]),
+            ("bytes=0-9,5-19", vec![range(0, 19)]),
+            ("bytes=10-19,0-9", vec![range(0, 19)]),
+            ("bytes=-10,0-9", vec![range(0, 9), range(990, 999)]),
+            ("bytes=0-99,10-19,200-", vec![range(0, 99), range(200, 999)]),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(parse_range(value, 1000), Some(expected), "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_parse_range_unsatisfiable() {
+        for value in ["bytes=1000-", "bytes=5000-6000", "bytes=-0"] {
+            assert_eq!(parse_range(value, 1000), Some(vec![]), "{}", value);
+        }
+
+        assert_eq!(parse_range("bytes=-10", 0), Some(vec![]));
+        assert_eq!(
+            parse_range("bytes=99999999999999999999999-", 1000),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test_parse_range_invalid() {
+        let too_many =
+            format!("bytes={}", vec!["0-1"; MAX_RANGES + 1].join(","));
+
+        let values = [
+            "bytes=",
+            "bytes=abc",
+            "bytes=500-100",
+            "bytes=-",
+            "bytes=+1-2",
+            "items=0-10",
+            "0-10",
+            too_many.as_str(),
+        ];
+
+        for value in values {
+            assert_eq!(parse_range(value, 1000), None, "{}", value);
+        }
+    }
+
+    #[test]
+    fn test_byte_range() {
+        let range = range(10, 19);
+
+        assert_eq!(range.length(), 10);
+        assert_eq!(range.content_range(100), "bytes 10-19/100");
+    }
+}
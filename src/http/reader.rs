@@ -0,0 +1,592 @@
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::time::timeout;
+
+use crate::errors::Errors;
+use crate::http::parser::{
+    chunk, chunk_size, is_token_char, request_line, trailers,
+};
+
+// Tamaño de cada lectura sobre el socket.
+const BUFFER_SIZE: usize = 1024 * 8;
+
+// Tamaño máximo de la línea de solicitud más las cabeceras.
+pub const MAX_HEAD_SIZE: usize = 1024 * 16;
+
+// Tamaño máximo de la URI de la línea de solicitud.
+pub const MAX_URI_LENGTH: usize = 1024 * 8;
+
+// Tamaño máximo del body de una solicitud.
+pub const MAX_BODY_SIZE: usize = 1024 * 1024 * 64;
+
+const HEAD_TERMINATOR: &[u8] = b"\r\n\r\n";
+
+// ---- -- Request Reader -- ---- \\
+
+/// Separa las solicitudes HTTP que llegan por un stream.
+///
+/// Lee hasta encontrar el fin de las cabeceras y luego exactamente
+/// `Content-Length` bytes de body (o hasta el último chunk si el body usa
+/// `Transfer-Encoding: chunked`), sin importar en cuántas lecturas
+/// lleguen. Los bytes sobrantes se conservan para la siguiente solicitud.
+///
+/// `idle_timeout` limita la espera del primer byte de cada solicitud y
+/// `request_timeout` la de cada lectura una vez que la solicitud empezó.
+pub struct RequestReader<R> {
+    stream: R,
+    buffer: Vec<u8>,
+    idle_timeout: Duration,
+    request_timeout: Duration,
+}
+
+impl<R: AsyncRead + Unpin> RequestReader<R> {
+    pub fn new(
+        stream: R,
+        idle_timeout: Duration,
+        request_timeout: Duration,
+    ) -> Self {
+        RequestReader {
+            stream,
+            buffer: Vec::new(),
+            idle_timeout,
+            request_timeout,
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.stream
+    }
+
+    /// Devuelve los bytes de la siguiente solicitud completa,
+    /// o `None` si el cliente cerró la conexión (o dejó de enviar datos)
+    /// entre dos solicitudes.
+    ///
+    /// Si la solicitud ya empezó y el cliente deja de enviar datos falla
+    /// con `Errors::RequestTimeout`, que se responde con 408.
+    pub async fn read_request(&mut self) -> Result<Option<Vec<u8>>, Errors> {
+        let head_size = loop {
+            // Las líneas vacías entre solicitudes se ignoran
+            let empty_lines = self
+                .buffer
+                .iter()
+                .take_while(|c| **c == b'\r' || **c == b'\n')
+                .count();
+            self.buffer.drain(..empty_lines);
+
+            // Los datos que no son HTTP se rechazan sin esperar al
+            // resto de las cabeceras
+            check_request_line(&self.buffer)?;
+
+            if let Some(position) = find(&self.buffer, HEAD_TERMINATOR) {
+                break position + HEAD_TERMINATOR.len();
+            }
+
+            if self.buffer.len() > MAX_HEAD_SIZE {
+                return Err(Errors::HeadersTooLarge);
+            }
+
+            match self.fill_buffer().await {
+                Ok(0) | Err(Errors::ReadTimeout) if self.buffer.is_empty() => {
+                    return Ok(None);
+                }
+                Ok(0) => return Err(Errors::IncompleteRequest),
+                Ok(_) => {}
+                Err(e) => return Err(e),
+            }
+        };
+
+        if head_size > MAX_HEAD_SIZE {
+            return Err(Errors::HeadersTooLarge);
+        }
+
+        let request_size = if is_chunked(&self.buffer[..head_size])? {
+            self.read_chunked_body(head_size).await?
+        } else {
+            self.read_body(head_size).await?
+        };
+
+        let request = self.buffer.drain(..request_size).collect();
+
+        Ok(Some(request))
+    }
+
+    /// Lee un body de `Content-Length` bytes que empieza en `head_size`,
+    /// devuelve el tamaño total de la solicitud.
+    async fn read_body(&mut self, head_size: usize) -> Result<usize, Errors> {
+        let body_size = content_length(&self.buffer[..head_size])?;
+
+        if body_size > MAX_BODY_SIZE {
+            return Err(Errors::PayloadTooLarge(body_size));
+        }
+
+        let request_size = head_size + body_size;
+
+        while self.buffer.len() < request_size {
+            if self.fill_buffer().await? == 0 {
+                return Err(Errors::IncompleteRequest);
+            }
+        }
+
+        Ok(request_size)
+    }
+
+    /// Lee un body en chunks que empieza en `head_size`, validando cada
+    /// chunk a medida que llega. Devuelve el tamaño total de la solicitud.
+    async fn read_chunked_body(
+        &mut self,
+        head_size: usize,
+    ) -> Result<usize, Errors> {
+        let mut position = head_size;
+        let mut body_size = 0;
+
+        // Chunks hasta el último, de tamaño 0
+        loop {
+            let input = &self.buffer[position..];
+
+            if let Ok((_, size)) = chunk_size(input) {
+                if body_size + size > MAX_BODY_SIZE {
+                    return Err(Errors::PayloadTooLarge(body_size + size));
+                }
+            }
+
+            let last_chunk = match chunk(input) {
+                Ok((rest, data)) => {
+                    body_size += data.len();
+                    position = self.buffer.len() - rest.len();
+
+                    Some(data.is_empty())
+                }
+                Err(nom::Err::Incomplete(_)) => None,
+                Err(_) => return Err(Errors::InvalidChunkedBody),
+            };
+
+            match last_chunk {
+                Some(true) => break,
+                Some(false) => continue,
+                None => self.fill_required().await?,
+            }
+        }
+
+        // Trailers hasta la línea vacía
+        loop {
+            let input = &self.buffer[position..];
+
+            match trailers(input) {
+                Ok((rest, _)) => return Ok(self.buffer.len() - rest.len()),
+                Err(nom::Err::Incomplete(_)) => {}
+                Err(_) => return Err(Errors::InvalidChunkedBody),
+            }
+
+            if input.len() > MAX_HEAD_SIZE {
+                return Err(Errors::HeadersTooLarge);
+            }
+
+            self.fill_required().await?;
+        }
+    }
+
+    /// Lee más datos cuando la solicitud actual todavía no está completa.
+    async fn fill_required(&mut self) -> Result<(), Errors> {
+        match self.fill_buffer().await? {
+            0 => Err(Errors::IncompleteRequest),
+            _ => Ok(()),
+        }
+    }
+
+    async fn fill_buffer(&mut self) -> Result<usize, Errors> {
+        let mut chunk = vec![0; BUFFER_SIZE];
+
+        // Sin bytes pendientes se está esperando una solicitud nueva
+        let (duration, error) = if self.buffer.is_empty() {
+            (self.idle_timeout, Errors::ReadTimeout)
+        } else {
+            (self.request_timeout, Errors::RequestTimeout)
+        };
+
+        let bytes_read = timeout(duration, self.stream.read(&mut chunk))
+            .await
+            .map_err(|_| error)??;
+
+        self.buffer.extend_from_slice(&chunk[..bytes_read]);
+
+        Ok(bytes_read)
+    }
+}
+
+/// Valida la línea de solicitud con los bytes recibidos hasta ahora.
+///
+/// El método debe ser un token y la URI no puede superar
+/// `MAX_URI_LENGTH`, una vez completa la línea se valida con el parser.
+fn check_request_line(buffer: &[u8]) -> Result<(), Errors> {
+    let line_end = buffer.iter().position(|c| *c == b'\n');
+    let line = &buffer[..line_end.map_or(buffer.len(), |end| end + 1)];
+
+    let method_end = line.iter().position(|c| *c == b' ');
+    let method = &line[..method_end.unwrap_or(line.len())];
+
+    if !method.iter().all(|c| is_token_char(*c)) {
+        return Err(Errors::MalformedRequest);
+    }
+
+    let Some(method_end) = method_end else {
+        return Ok(());
+    };
+
+    let uri = &line[method_end + 1..];
+    let uri_length = uri
+        .iter()
+        .position(|c| *c == b' ' || *c == b'\r' || *c == b'\n')
+        .unwrap_or(uri.len());
+
+    if uri_length > MAX_URI_LENGTH {
+        return Err(Errors::UriTooLong(uri_length));
+    }
+
+    if line_end.is_none() {
+        return Ok(());
+    }
+
+    match request_line(line) {
+        Ok((&[], _)) => Ok(()),
+        _ => Err(Errors::MalformedRequest),
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+/// Itera sobre los pares (nombre, valor) de las cabeceras.
+fn head_fields(head: &str) -> impl Iterator<Item = (&str, &str)> {
+    head.split("\r\n").skip(1).filter_map(|line| {
+        let (key, value) = line.split_once(':')?;
+
+        Some((key.trim(), value.trim()))
+    })
+}
+
+/// Indica si el body usa `Transfer-Encoding: chunked`.
+///
+/// Solo se soporta la codificación "chunked" por sí sola, cualquier otra
+/// combinación de codificaciones se rechaza.
+fn is_chunked(head: &[u8]) -> Result<bool, Errors> {
+    let head = String::from_utf8_lossy(head);
+    let mut codings = Vec::new();
+
+    for (key, value) in head_fields(&head) {
+        if key.eq_ignore_ascii_case("transfer-encoding") {
+            codings.extend(value.split(',').map(|s| s.trim().to_string()));
+        }
+    }
+
+    match codings.as_slice() {
+        [] => Ok(false),
+        [coding] if coding.eq_ignore_ascii_case("chunked") => Ok(true),
+        _ => Err(Errors::UnsupportedTransferEncoding(codings.join(", "))),
+    }
+}
+
+/// Obtiene el valor de la cabecera `Content-Length`, 0 si no está presente.
+///
+/// Varias cabeceras `Content-Length` solo se aceptan si tienen el mismo valor.
+fn content_length(head: &[u8]) -> Result<usize, Errors> {
+    let head = String::from_utf8_lossy(head);
+    let mut length: Option<usize> = None;
+
+    for (key, value) in head_fields(&head) {
+        if !key.eq_ignore_ascii_case("content-length") {
+            continue;
+        }
+
+        let is_number =
+            !value.is_empty() && value.bytes().all(|c| c.is_ascii_digit());
+
+        let parsed = match value.parse::<usize>() {
+            Ok(parsed) if is_number => parsed,
+            // Un número válido pero enorme se trata como body demasiado grande
+            Err(_) if is_number => {
+                return Err(Errors::PayloadTooLarge(usize::MAX))
+            }
+            _ => return Err(Errors::InvalidContentLength(value.to_string())),
+        };
+
+        match length {
+            Some(length) if length != parsed => {
+                return Err(Errors::InvalidContentLength(value.to_string()));
+            }
+            _ => length = Some(parsed),
+        }
+    }
+
+    Ok(length.unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tokio::io::AsyncWriteExt;
+
+    use crate::http::StatusCode;
+
+    const IDLE_TIMEOUT: Duration = Duration::from_millis(200);
+    const REQUEST_TIMEOUT: Duration = Duration::from_millis(600);
+
+    /// Crea un reader que recibe `input` en fragmentos de `segment` bytes.
+    fn segmented_reader(
+        input: &[u8],
+        segment: usize,
+    ) -> RequestReader<tokio::io::DuplexStream> {
+        let (mut client, server) = tokio::io::duplex(segment);
+        let input = input.to_vec();
+
+        tokio::spawn(async move {
+            client.write_all(&input).await.unwrap();
+        });
+
+        RequestReader::new(server, IDLE_TIMEOUT, REQUEST_TIMEOUT)
+    }
+
+    // ---- -- Content-Length -- ---- \\
+
+    #[test]
+    fn test_content_length_missing() {
+        let head = b"GET / HTTP/1.1\r\nHost: localhost\r\n\r\n";
+
+        assert_eq!(content_length(head).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_content_length_case_insensitive() {
+        let head = b"POST / HTTP/1.1\r\ncontent-length: 12\r\n\r\n";
+
+        assert_eq!(content_length(head).unwrap(), 12);
+    }
+
+    #[test]
+    fn test_content_length_invalid() {
+        for value in ["abc", "-1", "", "1 2", "+5"] {
+            let head =
+                format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", value);
+
+            let err = content_length(head.as_bytes()).unwrap_err();
+
+            assert!(matches!(err, Errors::InvalidContentLength(_)));
+        }
+    }
+
+    #[test]
+    fn test_content_length_conflicting_values() {
+        let head = b"POST / HTTP/1.1\r\nContent-Length: 1\r\nContent-Length: 2\r\n\r\n";
+
+        let err = content_length(head).unwrap_err();
+
+        assert!(matches!(err, Errors::InvalidContentLength(_)));
+    }
+
+    // ---- -- Transfer-Encoding -- ---- \\
+
+    #[test]
+    fn test_is_chunked() {
+        let head = b"POST / HTTP/1.1\r\nTransfer-Encoding: Chunked\r\n\r\n";
+
+        assert!(is_chunked(head).unwrap());
+    }
+
+    #[test]
+    fn test_is_chunked_unsupported_coding() {
+        let head =
+            b"POST / HTTP/1.1\r\nTransfer-Encoding: gzip, chunked\r\n\r\n";
+
+        let err = is_chunked(head).unwrap_err();
+
+        assert!(matches!(err, Errors::UnsupportedTransferEncoding(_)));
+    }
+
+    // ---- -- Request Reader -- ---- \\
+
+    #[tokio::test]
+    async fn test_read_request_chunked_body_split_in_segments() {
+        let input = b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+            5;ext\r\nHello\r\n8\r\n, World!\r\n0\r\nChecksum: abc\r\n\r\n\
+            GET / HTTP/1.1\r\n\r\n";
+
+        let mut reader = segmented_reader(input, 3);
+
+        let first = reader.read_request().await.unwrap().unwrap();
+        assert!(first.ends_with(b"0\r\nChecksum: abc\r\n\r\n"));
+
+        let second = reader.read_request().await.unwrap().unwrap();
+        assert_eq!(second, b"GET / HTTP/1.1\r\n\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_read_request_chunked_body_invalid() {
+        let input = b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+            5\r\nHello, World!\r\n0\r\n\r\n";
+
+        let mut reader = segmented_reader(input, 1024);
+        let err = reader.read_request().await.unwrap_err();
+
+        assert!(matches!(err, Errors::InvalidChunkedBody));
+    }
+
+    #[tokio::test]
+    async fn test_read_request_body_split_in_segments() {
+        let body = "a".repeat(BUFFER_SIZE * 3);
+        let input = format!(
+            "POST /files/a HTTP/1.1\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+
+        let mut reader = segmented_reader(input.as_bytes(), 100);
+        let request = reader.read_request().await.unwrap().unwrap();
+
+        assert_eq!(request, input.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_read_request_keeps_remaining_bytes() {
+        let input = b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\nokGET / HTTP/1.1\r\n\r\n";
+
+        let mut reader = segmented_reader(input, 7);
+
+        let first = reader.read_request().await.unwrap().unwrap();
+        assert_eq!(first, b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\nok");
+
+        let second = reader.read_request().await.unwrap().unwrap();
+        assert_eq!(second, b"GET / HTTP/1.1\r\n\r\n");
+
+        assert!(reader.read_request().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_request_ignores_empty_lines_between_requests() {
+        let input = b"POST / HTTP/1.1\r\nContent-Length: 2\r\n\r\nok\r\n\
+            GET / HTTP/1.1\r\n\r\n";
+
+        let mut reader = segmented_reader(input, 1024);
+
+        reader.read_request().await.unwrap().unwrap();
+
+        let second = reader.read_request().await.unwrap().unwrap();
+        assert_eq!(second, b"GET / HTTP/1.1\r\n\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_read_request_payload_too_large() {
+        let input = format!(
+            "POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_SIZE + 1
+        );
+
+        let mut reader = segmented_reader(input.as_bytes(), 1024);
+        let err = reader.read_request().await.unwrap_err();
+
+        assert!(matches!(err, Errors::PayloadTooLarge(_)));
+    }
+
+    #[tokio::test]
+    async fn test_read_request_incomplete_body() {
+        let input = b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc";
+
+        let mut reader = segmented_reader(input, 1024);
+        let err = reader.read_request().await.unwrap_err();
+
+        assert!(matches!(err, Errors::IncompleteRequest));
+    }
+
+    #[tokio::test]
+    async fn test_read_request_headers_too_large() {
+        let input =
+            format!("GET / HTTP/1.1\r\nX-Big: {}", "a".repeat(MAX_HEAD_SIZE));
+
+        let mut reader = segmented_reader(input.as_bytes(), 1024);
+        let err = reader.read_request().await.unwrap_err();
+
+        assert!(matches!(err, Errors::HeadersTooLarge));
+    }
+
+    #[tokio::test]
+    async fn test_read_request_rejects_binary_data() {
+        // Inicio de un ClientHello de TLS, sin ningún fin de línea
+        let input = b"\x16\x03\x01\x02\x00\x01\x00\x01\xfc\x03\x03";
+
+        let mut reader = segmented_reader(input, 1024);
+        let err = reader.read_request().await.unwrap_err();
+
+        assert!(matches!(err, Errors::MalformedRequest));
+    }
+
+    #[tokio::test]
+    async fn test_read_request_invalid_request_line() {
+        let input = b"GET /index.html\r\nHost: localhost\r\n\r\n";
+
+        let mut reader = segmented_reader(input, 1024);
+        let err = reader.read_request().await.unwrap_err();
+
+        assert!(matches!(err, Errors::MalformedRequest));
+    }
+
+    #[tokio::test]
+    async fn test_read_request_uri_too_long() {
+        let input = format!("GET /{} HTTP/1.1", "a".repeat(MAX_URI_LENGTH));
+
+        let mut reader = segmented_reader(input.as_bytes(), 1024);
+        let err = reader.read_request().await.unwrap_err();
+
+        assert!(matches!(err, Errors::UriTooLong(_)));
+    }
+
+    // ---- -- Timeouts -- ---- \\
+
+    #[tokio::test]
+    async fn test_read_request_idle_timeout() {
+        let (_client, server) = tokio::io::duplex(1024);
+        let mut reader =
+            RequestReader::new(server, IDLE_TIMEOUT, REQUEST_TIMEOUT);
+
+        assert!(reader.read_request().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_request_slower_than_idle_timeout() {
+        let (mut client, server) = tokio::io::duplex(1024);
+        let mut reader =
+            RequestReader::new(server, IDLE_TIMEOUT, REQUEST_TIMEOUT);
+
+        tokio::spawn(async move {
+            client.write_all(b"GET / HT").await.unwrap();
+            tokio::time::sleep(IDLE_TIMEOUT * 2).await;
+            client.write_all(b"TP/1.1\r\n\r\n").await.unwrap();
+        });
+
+        let request = reader.read_request().await.unwrap().unwrap();
+        assert_eq!(request, b"GET / HTTP/1.1\r\n\r\n");
+    }
+
+    #[tokio::test]
+    async fn test_read_request_timeout() {
+        let inputs = [
+            &b"GET / HTTP/1.1\r\nHost: loc"[..],
+            b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc",
+        ];
+
+        for input in inputs {
+            // El cliente sigue conectado pero no envía el resto
+            let (mut client, server) = tokio::io::duplex(1024);
+            client.write_all(input).await.unwrap();
+
+            let mut reader =
+                RequestReader::new(server, IDLE_TIMEOUT, REQUEST_TIMEOUT);
+            let err = reader.read_request().await.unwrap_err();
+
+            assert!(matches!(err, Errors::RequestTimeout));
+            assert_eq!(err.status_code(), Some(StatusCode::RequestTimeout));
+        }
+    }
+}
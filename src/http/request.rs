@@ -1,4 +1,9 @@
-use crate::http::{parse_request, UriPath, SUPPORTED_ENCODEING};
+use std::collections::HashMap;
+
+use crate::errors::Errors;
+use crate::http::percent_encoding::percent_decode;
+use crate::http::reader::MAX_BODY_SIZE;
+use crate::http::{parse_request, ContentCoding, HeaderMap, UriPath};
 
 // ---- -- HTTP Method -- ---- \\
 
@@ -6,17 +11,50 @@ use crate::http::{parse_request, UriPath, SUPPORTED_ENCODEING};
 #[derive(Debug, Clone, PartialEq)]
 pub enum HttpMethod {
     Get,
+    Head,
     Post,
-    // Método no inicializado o desconocido
-    Uninitialized,
+    Put,
+    Delete,
+    Patch,
+    Options,
+    Connect,
+    Trace,
+    // Cualquier otro método, como PROPFIND de WebDAV. Los métodos
+    // distinguen mayúsculas, "get" también es una extensión.
+    Extension(String),
 }
 
 impl From<&str> for HttpMethod {
     fn from(s: &str) -> HttpMethod {
         match s {
             "GET" => HttpMethod::Get,
+            "HEAD" => HttpMethod::Head,
             "POST" => HttpMethod::Post,
-            _ => HttpMethod::Uninitialized,
+            "PUT" => HttpMethod::Put,
+            "DELETE" => HttpMethod::Delete,
+            "PATCH" => HttpMethod::Patch,
+            "OPTIONS" => HttpMethod::Options,
+            "CONNECT" => HttpMethod::Connect,
+            "TRACE" => HttpMethod::Trace,
+            method => HttpMethod::Extension(method.to_string()),
+        }
+    }
+}
+
+impl HttpMethod {
+    /// Nombre del método tal como se escribe en la solicitud.
+    pub fn as_str(&self) -> &str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Patch => "PATCH",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Connect => "CONNECT",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Extension(method) => method,
         }
     }
 }
@@ -26,6 +64,7 @@ impl From<&str> for HttpMethod {
 // Representa la versión de protocolo HTTP de una solicitud.
 #[derive(Debug, Clone, PartialEq)]
 pub enum HttpVersion {
+    V1_0,
     V1_1,
     // Version no inicializada o desconocida
     Uninitialized,
@@ -34,6 +73,7 @@ pub enum HttpVersion {
 impl From<&str> for HttpVersion {
     fn from(s: &str) -> HttpVersion {
         match s {
+            "1.0" => HttpVersion::V1_0,
             "1.1" => HttpVersion::V1_1,
             _ => HttpVersion::Uninitialized,
         }
@@ -52,24 +92,30 @@ pub struct RequestLine {
 
 impl RequestLine {
     #[allow(dead_code)]
-    pub fn new(method: &str, uri: &str, http_version: &str) -> RequestLine {
-        RequestLine {
+    pub fn new(
+        method: &str,
+        uri: &str,
+        http_version: &str,
+    ) -> Result<RequestLine, Errors> {
+        Ok(RequestLine {
             method: HttpMethod::from(method),
-            uri: UriPath::new(uri),
+            uri: UriPath::new(uri)?,
             http_version: HttpVersion::from(http_version),
-        }
+        })
     }
 }
 
 // ---- -- Http Request -- ---- \\
 
-use std::collections::HashMap;
-
 #[derive(Debug, Clone, PartialEq)]
 pub struct HttpRequest {
     pub request_line: RequestLine,
-    pub headers: HashMap<String, String>,
+    pub headers: HeaderMap,
     pub body: Option<Vec<u8>>,
+    // Cabeceras recibidas después de un body con `Transfer-Encoding: chunked`
+    pub trailers: HeaderMap,
+    // Parámetros de la ruta sin decodificar, los completa el `Router`
+    pub params: HashMap<String, String>,
 }
 
 impl HttpRequest {
@@ -79,43 +125,91 @@ impl HttpRequest {
     pub fn method(&self) -> &HttpMethod {
         &self.request_line.method
     }
-    #[allow(dead_code)]
     pub fn version(&self) -> &HttpVersion {
         &self.request_line.http_version
     }
+    /// Valor decodificado del parámetro `name` de la ruta registrada en
+    /// el `Router`, un "%2F" del parámetro queda como "/".
+    pub fn param(&self, name: &str) -> Option<String> {
+        let value = self.raw_param(name)?;
+        let decoded = percent_decode(value);
+
+        // `UriPath` ya comprobó que cada segmento es UTF-8 válido
+        Some(String::from_utf8_lossy(&decoded).into_owned())
+    }
 
-    pub fn get_headers_for_the_response(&self) -> HashMap<String, String> {
-        let mut headers_response = HashMap::new();
+    /// Valor del parámetro `name` tal como llegó en la ruta, sin
+    /// decodificar.
+    pub fn raw_param(&self, name: &str) -> Option<&str> {
+        self.params.get(name).map(|value| value.as_str())
+    }
 
-        self.add_accept_encoding_header(&mut headers_response);
+    /// Indica si la conexión debe mantenerse abierta después de responder.
+    ///
+    /// La cabecera `Connection` tiene prioridad, si no está presente
+    /// HTTP/1.1 es persistente por defecto y HTTP/1.0 no.
+    pub fn keep_alive(&self) -> bool {
+        let options: Vec<&str> = self.headers.get_list("Connection").collect();
+        let has_option = |name: &str| {
+            options
+                .iter()
+                .any(|option| option.eq_ignore_ascii_case(name))
+        };
+
+        if has_option("close") {
+            return false;
+        }
+        if has_option("keep-alive") {
+            return true;
+        }
 
-        headers_response
+        *self.version() == HttpVersion::V1_1
     }
 
-    fn add_accept_encoding_header(&self, headers_response: &mut HashMap<String, String>) {
-        for (header, value) in &self.headers {
-            if header.to_lowercase() == "accept-encoding" {
-                let encodings: Vec<&str> = value.split(',').map(|s| s.trim()).collect();
-
-                for encoding in encodings {
-                    if SUPPORTED_ENCODEING.contains(&encoding) {
-                        // La cabecera Content-Encoding solo se
-                        // agrega si el tipo de codificación está
-                        // soportado por el servidor
-                        headers_response.insert(
-                            String::from("Content-Encoding"), String::from(encoding)
-                        );
-                    }
-                }
+    /// Decodifica el body según `Content-Encoding`, deshaciendo las
+    /// codificaciones en orden inverso al que se aplicaron.
+    ///
+    /// Después la solicitud queda como si el body hubiera llegado sin
+    /// codificar. El body decodificado tiene el mismo límite de tamaño
+    /// que uno recibido sin codificar.
+    pub fn decode_body(&mut self) -> Result<(), Errors> {
+        let codings = self
+            .headers
+            .get_list("Content-Encoding")
+            .map(|token| {
+                ContentCoding::from_token(token).ok_or_else(|| {
+                    Errors::UnsupportedContentEncoding(token.to_string())
+                })
+            })
+            .collect::<Result<Vec<ContentCoding>, Errors>>()?;
+
+        if codings.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(mut body) = self.body.take() {
+            for coding in codings.iter().rev() {
+                body = coding.decode(&body, MAX_BODY_SIZE)?;
+            }
 
+            if self.headers.contains_key("Content-Length") {
+                self.headers
+                    .insert("Content-Length", &body.len().to_string());
             }
+            self.body = if body.is_empty() { None } else { Some(body) };
         }
+
+        self.headers.remove("Content-Encoding");
+
+        Ok(())
     }
 }
 
-impl From<&[u8]> for HttpRequest {
-    fn from(request: &[u8]) -> Self {
-        parse_request(request).unwrap()
+impl TryFrom<&[u8]> for HttpRequest {
+    type Error = Errors;
+
+    fn try_from(request: &[u8]) -> Result<Self, Self::Error> {
+        parse_request(request)
     }
 }
 
@@ -123,6 +217,8 @@ impl From<&[u8]> for HttpRequest {
 mod tests {
     use super::*;
 
+    use crate::http::{Body, StatusCode};
+
     // ---- -- HttpMethod -- ---- \\
 
     #[test]
@@ -132,6 +228,19 @@ mod tests {
         assert_eq!(m, HttpMethod::Get);
     }
 
+    #[test]
+    fn test_method_extension() {
+        for method in ["PROPFIND", "get", "M-SEARCH"] {
+            let m: HttpMethod = method.into();
+
+            assert_eq!(m, HttpMethod::Extension(method.to_string()));
+            assert_eq!(m.as_str(), method);
+        }
+
+        let m: HttpMethod = "DELETE".into();
+        assert_eq!(m.as_str(), "DELETE");
+    }
+
     // ---- -- HttpVersion -- ---- \\
 
     #[test]
@@ -143,15 +252,119 @@ mod tests {
 
     // ---- -- HttpRequest -- ---- \\
 
+    // Headers
+
+    #[test]
+    fn test_read_http_get_with_repeated_headers() {
+        let request_lines = [
+            "GET / HTTP/1.1",
+            "Cookie: a=1",
+            "accept: text/html",
+            "cookie: b=2",
+            "",
+            "",
+        ];
+        let plain_request: String = request_lines.join("\r\n");
+
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
+
+        let cookies: Vec<&str> = request.headers.get_all("Cookie").collect();
+        assert_eq!(cookies, ["a=1", "b=2"]);
+        assert_eq!(request.headers.get("Accept"), Some("text/html"));
+    }
+
+    // Content-Encoding
+
+    fn encoded_request(content_encoding: &str, body: &[u8]) -> Vec<u8> {
+        let head = format!(
+            "POST /files/a HTTP/1.1\r\nContent-Encoding: {}\r\nContent-Length: {}\r\n\r\n",
+            content_encoding,
+            body.len()
+        );
+
+        [head.as_bytes(), body].concat()
+    }
+
+    #[test]
+    fn test_decode_body() {
+        let text = b"pear raspberry apple".to_vec();
+        let gzip = ContentCoding::Gzip.encode_body(text.clone().into());
+        let Body::Full(gzip) = gzip else {
+            panic!("Expected a full body");
+        };
+        let deflate = ContentCoding::Deflate.encode_body(gzip.into());
+        let Body::Full(deflate) = deflate else {
+            panic!("Expected a full body");
+        };
+
+        let plain_request =
+            encoded_request("gzip, identity, deflate", &deflate);
+        let mut request = HttpRequest::try_from(&plain_request[..]).unwrap();
+        request.decode_body().unwrap();
+
+        assert_eq!(request.body, Some(text));
+        assert_eq!(request.headers.get("Content-Encoding"), None);
+        assert_eq!(request.headers.content_length(), Some(20));
+    }
+
+    #[test]
+    fn test_decode_body_unsupported_coding() {
+        let plain_request = encoded_request("br", b"abc");
+        let mut request = HttpRequest::try_from(&plain_request[..]).unwrap();
+
+        let err = request.decode_body().unwrap_err();
+
+        assert!(matches!(err, Errors::UnsupportedContentEncoding(_)));
+        assert_eq!(err.status_code(), Some(StatusCode::UnsupportedMediaType));
+    }
+
+    // Keep-alive
+
+    #[test]
+    fn test_keep_alive_default_http_1_1() {
+        let plain_request = "GET / HTTP/1.1\r\n\r\n";
+
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
+
+        assert!(request.keep_alive());
+    }
+
+    #[test]
+    fn test_keep_alive_default_http_1_0() {
+        let plain_request = "GET / HTTP/1.0\r\n\r\n";
+
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
+
+        assert!(!request.keep_alive());
+    }
+
+    #[test]
+    fn test_keep_alive_connection_close() {
+        let plain_request = "GET / HTTP/1.1\r\nConnection: close\r\n\r\n";
+
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
+
+        assert!(!request.keep_alive());
+    }
+
+    #[test]
+    fn test_keep_alive_connection_keep_alive_http_1_0() {
+        let plain_request = "GET / HTTP/1.0\r\nconnection: Keep-Alive\r\n\r\n";
+
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
+
+        assert!(request.keep_alive());
+    }
+
     // GET
 
     #[test]
     fn test_read_http_get_empty_request() {
         let plain_request: String = String::from("GET / HTTP/1.1\r\n\r\n");
 
-        let headers_expected = HashMap::new();
+        let headers_expected = HeaderMap::new();
 
-        let request: HttpRequest = plain_request.as_bytes().into();
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
 
         assert_eq!(HttpMethod::Get, request.method().clone());
         assert_eq!(HttpVersion::V1_1, request.version().clone());
@@ -171,17 +384,17 @@ mod tests {
         ];
         let plain_request: String = request_lines.join("\r\n");
 
-        let mut headers_expected = HashMap::new();
-        headers_expected.insert("Host".into(), "localhost:3000".into());
-        headers_expected.insert("Accept".into(), "*/*".into());
-        headers_expected.insert("User-Agent".into(), "curl/7.64.1".into());
+        let mut headers_expected = HeaderMap::new();
+        headers_expected.insert("Host", "localhost:3000");
+        headers_expected.insert("Accept", "*/*");
+        headers_expected.insert("User-Agent", "curl/7.64.1");
 
         // Ordenar los elementos del mapa headers_expected
         let mut sorted_headers_expected: Vec<_> =
             headers_expected.into_iter().collect();
         sorted_headers_expected.sort();
 
-        let request: HttpRequest = plain_request.as_bytes().into();
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
 
         assert_eq!(HttpMethod::Get, request.method().clone());
         assert_eq!(HttpVersion::V1_1, request.version().clone());
@@ -200,9 +413,9 @@ mod tests {
         let request_lines = ["GET /api/data HTTP/1.1", "\r\n"];
         let plain_request: String = request_lines.join("\r\n");
 
-        let headers_expected = HashMap::new();
+        let headers_expected = HeaderMap::new();
 
-        let request: HttpRequest = plain_request.as_bytes().into();
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
 
         assert_eq!(HttpMethod::Get, request.method().clone());
         assert_eq!(HttpVersion::V1_1, request.version().clone());
@@ -217,22 +430,22 @@ mod tests {
         let request_lines = [
             "GET /data HTTP/1.1",
             "Host: example.com",
-            "Content-Length: 15",
-            "\r\n",
+            "Content-Length: 13",
+            "",
             "Hello, World!",
         ];
         let plain_request: String = request_lines.join("\r\n");
 
-        let mut headers_expected = HashMap::new();
-        headers_expected.insert("Host".into(), "example.com".into());
-        headers_expected.insert("Content-Length".into(), "15".into());
+        let mut headers_expected = HeaderMap::new();
+        headers_expected.insert("Host", "example.com");
+        headers_expected.insert("Content-Length", "13");
 
         // Ordenar los elementos del mapa headers_expected
         let mut sorted_headers_expected: Vec<_> =
             headers_expected.into_iter().collect();
         sorted_headers_expected.sort();
 
-        let request = HttpRequest::from(plain_request.as_bytes());
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
 
         assert_eq!(HttpMethod::Get, request.method().clone());
         assert_eq!(HttpVersion::V1_1, request.version().clone());
@@ -253,9 +466,9 @@ mod tests {
     fn test_read_http_post_empty_request() {
         let plain_request: String = String::from("POST / HTTP/1.1\r\n\r\n");
 
-        let headers_expected = HashMap::new();
+        let headers_expected = HeaderMap::new();
 
-        let request: HttpRequest = plain_request.as_bytes().into();
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
 
         assert_eq!(HttpMethod::Post, request.method().clone());
         assert_eq!(HttpVersion::V1_1, request.version().clone());
@@ -276,17 +489,17 @@ mod tests {
         ];
         let plain_request: String = request_lines.join("\r\n");
 
-        let mut headers_expected = HashMap::new();
-        headers_expected.insert("Host".into(), "197.0.0.1:3000".into());
-        headers_expected.insert("Accept".into(), "*/*".into());
-        headers_expected.insert("User-Agent".into(), "curl/7.64.1".into());
+        let mut headers_expected = HeaderMap::new();
+        headers_expected.insert("Host", "197.0.0.1:3000");
+        headers_expected.insert("Accept", "*/*");
+        headers_expected.insert("User-Agent", "curl/7.64.1");
 
         // Ordenar los elementos del mapa headers_expected
         let mut sorted_headers_expected: Vec<_> =
             headers_expected.into_iter().collect();
         sorted_headers_expected.sort();
 
-        let request: HttpRequest = plain_request.as_bytes().into();
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
 
         assert_eq!(HttpMethod::Post, request.method().clone());
         assert_eq!(HttpVersion::V1_1, request.version().clone());
@@ -305,9 +518,9 @@ mod tests {
         let request_lines = ["POST /api/data HTTP/1.1", "\r\n"];
         let plain_request: String = request_lines.join("\r\n");
 
-        let headers_expected = HashMap::new();
+        let headers_expected = HeaderMap::new();
 
-        let request: HttpRequest = plain_request.as_bytes().into();
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
 
         assert_eq!(HttpMethod::Post, request.method().clone());
         assert_eq!(HttpVersion::V1_1, request.version().clone());
@@ -321,22 +534,22 @@ mod tests {
         let request_lines = [
             "POST /data HTTP/1.1",
             "Host: example.com",
-            "Content-Length: 15",
-            "\r\n",
+            "Content-Length: 13",
+            "",
             "Hello, World!",
         ];
         let plain_request: String = request_lines.join("\r\n");
 
-        let mut headers_expected = HashMap::new();
-        headers_expected.insert("Host".into(), "example.com".into());
-        headers_expected.insert("Content-Length".into(), "15".into());
+        let mut headers_expected = HeaderMap::new();
+        headers_expected.insert("Host", "example.com");
+        headers_expected.insert("Content-Length", "13");
 
         // Ordenar los elementos del mapa headers_expected
         let mut sorted_headers_expected: Vec<_> =
             headers_expected.into_iter().collect();
         sorted_headers_expected.sort();
 
-        let request: HttpRequest = plain_request.as_bytes().into();
+        let request = HttpRequest::try_from(plain_request.as_bytes()).unwrap();
 
         assert_eq!(HttpMethod::Post, request.method().clone());
         assert_eq!(HttpVersion::V1_1, request.version().clone());
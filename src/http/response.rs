@@ -1,154 +1,191 @@
-use std::collections::HashMap;
-use std::fmt::Write;
 use std::io::Result;
 
-use tokio::io::AsyncWriteExt;
-use tokio::net::TcpStream;
+use tokio::io::{AsyncRead, AsyncWrite, AsyncWriteExt};
 
-#[derive(Debug, PartialEq, Clone)]
+use crate::http::{Body, HeaderMap, HttpVersion, StatusCode};
+
+#[derive(Debug, PartialEq)]
 pub struct HttpResponse {
     version: String,
-    status_code: String,
-    status_text: String,
-    headers: Option<HashMap<String, String>>,
-    body: Option<Vec<u8>>,
+    status_code: StatusCode,
+    headers: HeaderMap,
+    body: Body,
+    // `false` en las respuestas a HEAD: se envían las mismas cabeceras,
+    // incluyendo `Content-Length`, pero ningún byte del body
+    send_body: bool,
 }
 
 impl Default for HttpResponse {
     fn default() -> Self {
         Self {
             version: "HTTP/1.1".to_string(),
-            status_code: "200".to_string(),
-            status_text: "OK".to_string(),
-            headers: None,
-            body: None,
+            status_code: StatusCode::Ok,
+            headers: HeaderMap::new(),
+            body: Body::Empty,
+            send_body: true,
         }
     }
 }
 
 impl HttpResponse {
     pub fn new(
-        status_code: &str,
-        headers: Option<HashMap<String, String>>,
+        status_code: StatusCode,
+        headers: Option<HeaderMap>,
         body: Option<&[u8]>,
     ) -> HttpResponse {
-        let mut response: HttpResponse = HttpResponse::default();
-
-        if status_code != "200" {
-            response.status_code = status_code.into();
+        let headers = match headers {
+            Some(headers) => headers,
+            None => HeaderMap::from([("Content-type", "text/html")]),
         };
 
-        response.headers = match &headers {
-            Some(_h) => headers,
-            None => {
-                let mut headers = HashMap::new();
-                headers.insert(
-                    "Content-type".to_string(),
-                    "text/html".to_string(),
-                );
-
-                Some(headers)
-            }
+        let body = match body {
+            Some(b) => Body::Full(b.to_vec()),
+            None => Body::Empty,
         };
 
-        response.status_text = match response.status_code.as_str() {
-            "200" => "OK".to_string(),
-            "201" => "Created".to_string(),
-            "400" => "Bad Request".to_string(),
-            "404" => "Not Found".to_string(),
-            "500" => "Internal Server Error".to_string(),
-            _ => "Not Found".to_string(),
-        };
+        HttpResponse {
+            status_code,
+            headers,
+            body,
+            ..HttpResponse::default()
+        }
+    }
 
-        response.body = body.map(|b| b.to_vec());
+    /// Crea una respuesta cuyo body se lee de `reader` al momento de enviarla.
+    ///
+    /// Si `length` es `None` el body se envía con
+    /// `Transfer-Encoding: chunked`.
+    pub fn new_stream<R>(
+        status_code: StatusCode,
+        headers: Option<HeaderMap>,
+        reader: R,
+        length: Option<u64>,
+    ) -> HttpResponse
+    where
+        R: AsyncRead + Send + Unpin + 'static,
+    {
+        let mut response = HttpResponse::new(status_code, headers, None);
+        response.body = Body::stream(reader, length);
 
         response
     }
 
-    pub async fn send_response(
-        &self,
-        write_stream: &mut TcpStream,
-    ) -> Result<()> {
-        let response = self.clone();
-        let response_bytes = Vec::<u8>::from(response);
-        // let response_string: String = String::from(response);
-
-        write_stream
-            .write_all(response_bytes.as_slice())
-            .await
-            .unwrap();
+    /// Agrega (o reemplaza) una cabecera en la respuesta.
+    pub fn add_header(&mut self, key: &str, value: &str) {
+        self.headers.insert(key, value);
+    }
 
-        Ok(())
+    /// Quita el body de la respuesta, dejándola vacía.
+    pub fn take_body(&mut self) -> Body {
+        std::mem::replace(&mut self.body, Body::Empty)
     }
 
-    #[allow(dead_code)]
-    fn version(&self) -> String {
-        self.version.to_string()
+    pub fn set_body(&mut self, body: Body) {
+        self.body = body;
     }
 
-    #[allow(dead_code)]
-    fn status_code(&self) -> String {
-        self.status_code.to_string()
+    pub fn headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.headers
     }
 
-    #[allow(dead_code)]
-    fn status_text(&self) -> String {
-        self.status_text.to_string()
+    /// Evita que se envíe el body, manteniendo las cabeceras que lo
+    /// describen. Se usa para responder a HEAD.
+    pub fn skip_body(&mut self) {
+        self.send_body = false;
     }
 
-    #[allow(dead_code)]
-    fn headers(&self) -> String {
-        match &self.headers {
-            Some(map) => {
-                let mut header_string = String::new();
-                for (k, v) in map {
-                    write!(header_string, "{}: {}\r\n", k, v).unwrap();
-                }
+    /// Responde con la versión de la solicitud, así un cliente HTTP/1.0
+    /// no recibe un body con `Transfer-Encoding: chunked`.
+    pub fn set_version(&mut self, version: &HttpVersion) {
+        self.version = match version {
+            HttpVersion::V1_0 => "HTTP/1.0".to_string(),
+            _ => "HTTP/1.1".to_string(),
+        };
+    }
 
-                header_string
-            }
-            None => String::new(),
-        }
+    /// Indica si el final del body se marca cerrando la conexión.
+    ///
+    /// Pasa con HTTP/1.0 cuando no se conoce la longitud del body, ya
+    /// que esa versión no tiene `Transfer-Encoding: chunked`
+    /// (RFC 9112, sección 6.1).
+    pub fn is_close_delimited(&self) -> bool {
+        self.version == "HTTP/1.0"
+            && self.send_body
+            && self.status_code.allows_body()
+            && self.body.length().is_none()
     }
 
-    pub fn body(&self) -> Vec<u8> {
-        match &self.body {
-            Some(b) => b.clone(),
-            None => Vec::new(),
+    pub async fn send_response<W>(self, write_stream: &mut W) -> Result<()>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        write_stream.write_all(&self.head()).await?;
+
+        if self.is_close_delimited() {
+            self.body.write_unframed(write_stream).await?;
+        } else if self.send_body {
+            self.body.write_to(write_stream).await?;
         }
+
+        write_stream.flush().await?;
+
+        Ok(())
     }
-}
 
-impl From<HttpResponse> for Vec<u8> {
-    fn from(response: HttpResponse) -> Vec<u8> {
+    /// Serializa la línea de estado y las cabeceras, incluyendo la que
+    /// indica cómo se delimita el body.
+    fn head(&self) -> Vec<u8> {
         let mut result = Vec::new();
 
-        result.extend_from_slice(response.version.as_bytes());
+        result.extend_from_slice(self.version.as_bytes());
         result.extend_from_slice(b" ");
-        result.extend_from_slice(response.status_code.as_bytes());
+        result.extend_from_slice(self.status_code.to_string().as_bytes());
         result.extend_from_slice(b" ");
-        result.extend_from_slice(response.status_text.as_bytes());
+        result.extend_from_slice(self.status_code.reason_phrase().as_bytes());
         result.extend_from_slice(b"\r\n");
 
-        if let Some(headers) = &response.headers {
-            for (key, value) in headers {
-                result.extend_from_slice(key.as_bytes());
-                result.extend_from_slice(b": ");
-                result.extend_from_slice(value.as_bytes());
-                result.extend_from_slice(b"\r\n");
-            }
+        for (key, value) in self.headers.iter() {
+            result.extend_from_slice(key.as_bytes());
+            result.extend_from_slice(b": ");
+            result.extend_from_slice(value.as_bytes());
+            result.extend_from_slice(b"\r\n");
         }
-        result.extend_from_slice(b"Content-Length: ");
-        result.extend_from_slice(response.body().len().to_string().as_bytes());
 
-        result.extend_from_slice(b"\r\n\r\n");
+        // Las respuestas sin body tampoco indican cómo se delimita, ni
+        // las que lo delimitan cerrando la conexión
+        if self.status_code.allows_body() && !self.is_close_delimited() {
+            match self.body.length() {
+                Some(length) => {
+                    result.extend_from_slice(b"Content-Length: ");
+                    result.extend_from_slice(length.to_string().as_bytes());
+                }
+                None => result.extend_from_slice(b"Transfer-Encoding: chunked"),
+            }
 
-        if let Some(body) = &response.body {
-            result.extend_from_slice(body);
+            result.extend_from_slice(b"\r\n");
         }
 
+        result.extend_from_slice(b"\r\n");
+
         result
     }
+
+    #[allow(dead_code)]
+    fn version(&self) -> String {
+        self.version.to_string()
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        self.status_code
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn body(&self) -> &Body {
+        &self.body
+    }
 }
 
 #[cfg(test)]
@@ -160,25 +197,24 @@ mod tests {
         let default_response = HttpResponse::default();
 
         assert_eq!(default_response.version, "HTTP/1.1");
-        assert_eq!(default_response.status_code, "200");
-        assert_eq!(default_response.status_text, "OK");
-        assert_eq!(default_response.headers, None);
-        assert_eq!(default_response.body, None);
+        assert_eq!(default_response.status_code, StatusCode::Ok);
+        assert_eq!(default_response.headers, HeaderMap::new());
+        assert_eq!(default_response.body, Body::Empty);
     }
 
     #[test]
     fn test_response_creation_200() {
-        let status_code = "200";
+        let status_code = StatusCode::Ok;
         let body = "Item was shipped on 21st Dec 2020";
-        let mut headers = HashMap::new();
-        headers.insert("Content-type".to_string(), "text/plain".to_string());
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-type", "text/plain");
 
         let expected_response = HttpResponse {
             version: "HTTP/1.1".to_string(),
-            status_code: status_code.to_string(),
-            status_text: "OK".to_string(),
-            headers: Some(headers.clone()),
-            body: Some(body.as_bytes().to_vec()),
+            status_code,
+            headers: headers.clone(),
+            body: Body::Full(body.as_bytes().to_vec()),
+            send_body: true,
         };
 
         let response = HttpResponse::new(
@@ -192,21 +228,150 @@ mod tests {
 
     #[test]
     fn test_response_struct_creation_404() {
-        let status_code = "404";
+        let status_code = StatusCode::NotFound;
         let body = "Item was shipped on 21st Dec 2020";
-        let mut headers = HashMap::new();
-        headers.insert("Content-type".to_string(), "text/html".to_string());
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-type", "text/html");
 
         let expected_response = HttpResponse {
             version: "HTTP/1.1".to_string(),
-            status_code: status_code.to_string(),
-            status_text: "Not Found".to_string(),
-            headers: Some(headers.clone()),
-            body: Some(body.as_bytes().to_vec()),
+            status_code,
+            headers: headers.clone(),
+            body: Body::Full(body.as_bytes().to_vec()),
+            send_body: true,
         };
 
         let response =
             HttpResponse::new(status_code, None, Some(body.as_bytes()));
         assert_eq!(response, expected_response);
     }
+
+    #[test]
+    fn test_add_header_without_headers() {
+        let mut response = HttpResponse::default();
+
+        response.add_header("Connection", "close");
+
+        let headers = HeaderMap::from([("Connection", "close")]);
+        assert_eq!(response.headers, headers);
+    }
+
+    #[tokio::test]
+    async fn test_send_response_with_full_body() {
+        let response = HttpResponse::new(StatusCode::Ok, None, Some(b"Hello"));
+        let mut output = Vec::new();
+
+        response.send_response(&mut output).await.unwrap();
+
+        let expected = [
+            "HTTP/1.1 200 OK",
+            "Content-type: text/html",
+            "Content-Length: 5",
+            "",
+            "Hello",
+        ];
+        assert_eq!(String::from_utf8(output).unwrap(), expected.join("\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_send_response_with_stream_of_unknown_length() {
+        let response =
+            HttpResponse::new_stream(StatusCode::Ok, None, &b"Hello"[..], None);
+        let mut output = Vec::new();
+
+        response.send_response(&mut output).await.unwrap();
+
+        let expected = [
+            "HTTP/1.1 200 OK",
+            "Content-type: text/html",
+            "Transfer-Encoding: chunked",
+            "",
+            "5",
+            "Hello",
+            "0",
+            "",
+            "",
+        ];
+        assert_eq!(String::from_utf8(output).unwrap(), expected.join("\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_send_response_http_1_0_stream_is_close_delimited() {
+        let mut response =
+            HttpResponse::new_stream(StatusCode::Ok, None, &b"Hello"[..], None);
+        let mut output = Vec::new();
+
+        response.set_version(&HttpVersion::V1_0);
+        assert!(response.is_close_delimited());
+        response.send_response(&mut output).await.unwrap();
+
+        let expected =
+            ["HTTP/1.0 200 OK", "Content-type: text/html", "", "Hello"];
+        assert_eq!(String::from_utf8(output).unwrap(), expected.join("\r\n"));
+    }
+
+    #[test]
+    fn test_http_1_0_known_length_is_not_close_delimited() {
+        let mut response = HttpResponse::new(StatusCode::Ok, None, Some(b"a"));
+        response.set_version(&HttpVersion::V1_0);
+
+        assert!(!response.is_close_delimited());
+
+        let mut head_response =
+            HttpResponse::new_stream(StatusCode::Ok, None, &b"a"[..], None);
+        head_response.set_version(&HttpVersion::V1_0);
+        head_response.skip_body();
+
+        assert!(!head_response.is_close_delimited());
+    }
+
+    #[tokio::test]
+    async fn test_send_response_status_line_uses_reason_phrase() {
+        let response =
+            HttpResponse::new(StatusCode::MovedPermanently, None, None);
+        let mut output = Vec::new();
+
+        response.send_response(&mut output).await.unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("HTTP/1.1 301 Moved Permanently\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_send_response_without_body_omits_framing() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Allow", "GET, OPTIONS");
+        let response =
+            HttpResponse::new(StatusCode::NoContent, Some(headers), None);
+        let mut output = Vec::new();
+
+        response.send_response(&mut output).await.unwrap();
+
+        let expected =
+            ["HTTP/1.1 204 No Content", "Allow: GET, OPTIONS", "", ""];
+        assert_eq!(String::from_utf8(output).unwrap(), expected.join("\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_send_response_skip_body_keeps_content_length() {
+        let mut response = HttpResponse::new_stream(
+            StatusCode::Ok,
+            None,
+            &b"Hello"[..],
+            Some(5),
+        );
+        let mut output = Vec::new();
+
+        response.skip_body();
+        response.send_response(&mut output).await.unwrap();
+
+        let expected = [
+            "HTTP/1.1 200 OK",
+            "Content-type: text/html",
+            "Content-Length: 5",
+            "",
+            "",
+        ];
+        assert_eq!(String::from_utf8(output).unwrap(), expected.join("\r\n"));
+    }
 }
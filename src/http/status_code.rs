@@ -0,0 +1,311 @@
+use std::fmt;
+
+use crate::errors::Errors;
+
+// ---- -- Status Code -- ---- \\
+
+/// Códigos de estado HTTP registrados en la IANA
+/// (<https://www.iana.org/assignments/http-status-codes>).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatusCode {
+    Continue = 100,
+    SwitchingProtocols = 101,
+    Processing = 102,
+    EarlyHints = 103,
+    Ok = 200,
+    Created = 201,
+    Accepted = 202,
+    NonAuthoritativeInformation = 203,
+    NoContent = 204,
+    ResetContent = 205,
+    PartialContent = 206,
+    MultiStatus = 207,
+    AlreadyReported = 208,
+    ImUsed = 226,
+    MultipleChoices = 300,
+    MovedPermanently = 301,
+    Found = 302,
+    SeeOther = 303,
+    NotModified = 304,
+    UseProxy = 305,
+    TemporaryRedirect = 307,
+    PermanentRedirect = 308,
+    BadRequest = 400,
+    Unauthorized = 401,
+    PaymentRequired = 402,
+    Forbidden = 403,
+    NotFound = 404,
+    MethodNotAllowed = 405,
+    NotAcceptable = 406,
+    ProxyAuthenticationRequired = 407,
+    RequestTimeout = 408,
+    Conflict = 409,
+    Gone = 410,
+    LengthRequired = 411,
+    PreconditionFailed = 412,
+    ContentTooLarge = 413,
+    UriTooLong = 414,
+    UnsupportedMediaType = 415,
+    RangeNotSatisfiable = 416,
+    ExpectationFailed = 417,
+    MisdirectedRequest = 421,
+    UnprocessableContent = 422,
+    Locked = 423,
+    FailedDependency = 424,
+    TooEarly = 425,
+    UpgradeRequired = 426,
+    PreconditionRequired = 428,
+    TooManyRequests = 429,
+    RequestHeaderFieldsTooLarge = 431,
+    UnavailableForLegalReasons = 451,
+    InternalServerError = 500,
+    NotImplemented = 501,
+    BadGateway = 502,
+    ServiceUnavailable = 503,
+    GatewayTimeout = 504,
+    HttpVersionNotSupported = 505,
+    VariantAlsoNegotiates = 506,
+    InsufficientStorage = 507,
+    LoopDetected = 508,
+    NotExtended = 510,
+    NetworkAuthenticationRequired = 511,
+}
+
+impl StatusCode {
+    pub fn as_u16(&self) -> u16 {
+        *self as u16
+    }
+
+    /// Frase de razón que acompaña al código en la línea de estado.
+    pub fn reason_phrase(&self) -> &'static str {
+        match self {
+            StatusCode::Continue => "Continue",
+            StatusCode::SwitchingProtocols => "Switching Protocols",
+            StatusCode::Processing => "Processing",
+            StatusCode::EarlyHints => "Early Hints",
+            StatusCode::Ok => "OK",
+            StatusCode::Created => "Created",
+            StatusCode::Accepted => "Accepted",
+            StatusCode::NonAuthoritativeInformation => {
+                "Non-Authoritative Information"
+            }
+            StatusCode::NoContent => "No Content",
+            StatusCode::ResetContent => "Reset Content",
+            StatusCode::PartialContent => "Partial Content",
+            StatusCode::MultiStatus => "Multi-Status",
+            StatusCode::AlreadyReported => "Already Reported",
+            StatusCode::ImUsed => "IM Used",
+            StatusCode::MultipleChoices => "Multiple Choices",
+            StatusCode::MovedPermanently => "Moved Permanently",
+            StatusCode::Found => "Found",
+            StatusCode::SeeOther => "See Other",
+            StatusCode::NotModified => "Not Modified",
+            StatusCode::UseProxy => "Use Proxy",
+            StatusCode::TemporaryRedirect => "Temporary Redirect",
+            StatusCode::PermanentRedirect => "Permanent Redirect",
+            StatusCode::BadRequest => "Bad Request",
+            StatusCode::Unauthorized => "Unauthorized",
+            StatusCode::PaymentRequired => "Payment Required",
+            StatusCode::Forbidden => "Forbidden",
+            StatusCode::NotFound => "Not Found",
+            StatusCode::MethodNotAllowed => "Method Not Allowed",
+            StatusCode::NotAcceptable => "Not Acceptable",
+            StatusCode::ProxyAuthenticationRequired => {
+                "Proxy Authentication Required"
+            }
+            StatusCode::RequestTimeout => "Request Timeout",
+            StatusCode::Conflict => "Conflict",
+            StatusCode::Gone => "Gone",
+            StatusCode::LengthRequired => "Length Required",
+            StatusCode::PreconditionFailed => "Precondition Failed",
+            StatusCode::ContentTooLarge => "Content Too Large",
+            StatusCode::UriTooLong => "URI Too Long",
+            StatusCode::UnsupportedMediaType => "Unsupported Media Type",
+            StatusCode::RangeNotSatisfiable => "Range Not Satisfiable",
+            StatusCode::ExpectationFailed => "Expectation Failed",
+            StatusCode::MisdirectedRequest => "Misdirected Request",
+            StatusCode::UnprocessableContent => "Unprocessable Content",
+            StatusCode::Locked => "Locked",
+            StatusCode::FailedDependency => "Failed Dependency",
+            StatusCode::TooEarly => "Too Early",
+            StatusCode::UpgradeRequired => "Upgrade Required",
+            StatusCode::PreconditionRequired => "Precondition Required",
+            StatusCode::TooManyRequests => "Too Many Requests",
+            StatusCode::RequestHeaderFieldsTooLarge => {
+                "Request Header Fields Too Large"
+            }
+            StatusCode::UnavailableForLegalReasons => {
+                "Unavailable For Legal Reasons"
+            }
+            StatusCode::InternalServerError => "Internal Server Error",
+            StatusCode::NotImplemented => "Not Implemented",
+            StatusCode::BadGateway => "Bad Gateway",
+            StatusCode::ServiceUnavailable => "Service Unavailable",
+            StatusCode::GatewayTimeout => "Gateway Timeout",
+            StatusCode::HttpVersionNotSupported => "HTTP Version Not Supported",
+            StatusCode::VariantAlsoNegotiates => "Variant Also Negotiates",
+            StatusCode::InsufficientStorage => "Insufficient Storage",
+            StatusCode::LoopDetected => "Loop Detected",
+            StatusCode::NotExtended => "Not Extended",
+            StatusCode::NetworkAuthenticationRequired => {
+                "Network Authentication Required"
+            }
+        }
+    }
+
+    /// Indica si la respuesta puede llevar body: las respuestas 1xx,
+    /// 204 y 304 no lo tienen (RFC 9110, sección 6.4.1).
+    pub fn allows_body(&self) -> bool {
+        let no_body =
+            matches!(self, StatusCode::NoContent | StatusCode::NotModified);
+
+        !(self.is_informational() || no_body)
+    }
+
+    /// 1xx
+    pub fn is_informational(&self) -> bool {
+        (100..200).contains(&self.as_u16())
+    }
+
+    /// 5xx
+    pub fn is_server_error(&self) -> bool {
+        (500..600).contains(&self.as_u16())
+    }
+}
+
+impl TryFrom<u16> for StatusCode {
+    type Error = Errors;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        match code {
+            100 => Ok(StatusCode::Continue),
+            101 => Ok(StatusCode::SwitchingProtocols),
+            102 => Ok(StatusCode::Processing),
+            103 => Ok(StatusCode::EarlyHints),
+            200 => Ok(StatusCode::Ok),
+            201 => Ok(StatusCode::Created),
+            202 => Ok(StatusCode::Accepted),
+            203 => Ok(StatusCode::NonAuthoritativeInformation),
+            204 => Ok(StatusCode::NoContent),
+            205 => Ok(StatusCode::ResetContent),
+            206 => Ok(StatusCode::PartialContent),
+            207 => Ok(StatusCode::MultiStatus),
+            208 => Ok(StatusCode::AlreadyReported),
+            226 => Ok(StatusCode::ImUsed),
+            300 => Ok(StatusCode::MultipleChoices),
+            301 => Ok(StatusCode::MovedPermanently),
+            302 => Ok(StatusCode::Found),
+            303 => Ok(StatusCode::SeeOther),
+            304 => Ok(StatusCode::NotModified),
+            305 => Ok(StatusCode::UseProxy),
+            307 => Ok(StatusCode::TemporaryRedirect),
+            308 => Ok(StatusCode::PermanentRedirect),
+            400 => Ok(StatusCode::BadRequest),
+            401 => Ok(StatusCode::Unauthorized),
+            402 => Ok(StatusCode::PaymentRequired),
+            403 => Ok(StatusCode::Forbidden),
+            404 => Ok(StatusCode::NotFound),
+            405 => Ok(StatusCode::MethodNotAllowed),
+            406 => Ok(StatusCode::NotAcceptable),
+            407 => Ok(StatusCode::ProxyAuthenticationRequired),
+            408 => Ok(StatusCode::RequestTimeout),
+            409 => Ok(StatusCode::Conflict),
+            410 => Ok(StatusCode::Gone),
+            411 => Ok(StatusCode::LengthRequired),
+            412 => Ok(StatusCode::PreconditionFailed),
+            413 => Ok(StatusCode::ContentTooLarge),
+            414 => Ok(StatusCode::UriTooLong),
+            415 => Ok(StatusCode::UnsupportedMediaType),
+            416 => Ok(StatusCode::RangeNotSatisfiable),
+            417 => Ok(StatusCode::ExpectationFailed),
+            421 => Ok(StatusCode::MisdirectedRequest),
+            422 => Ok(StatusCode::UnprocessableContent),
+            423 => Ok(StatusCode::Locked),
+            424 => Ok(StatusCode::FailedDependency),
+            425 => Ok(StatusCode::TooEarly),
+            426 => Ok(StatusCode::UpgradeRequired),
+            428 => Ok(StatusCode::PreconditionRequired),
+            429 => Ok(StatusCode::TooManyRequests),
+            431 => Ok(StatusCode::RequestHeaderFieldsTooLarge),
+            451 => Ok(StatusCode::UnavailableForLegalReasons),
+            500 => Ok(StatusCode::InternalServerError),
+            501 => Ok(StatusCode::NotImplemented),
+            502 => Ok(StatusCode::BadGateway),
+            503 => Ok(StatusCode::ServiceUnavailable),
+            504 => Ok(StatusCode::GatewayTimeout),
+            505 => Ok(StatusCode::HttpVersionNotSupported),
+            506 => Ok(StatusCode::VariantAlsoNegotiates),
+            507 => Ok(StatusCode::InsufficientStorage),
+            508 => Ok(StatusCode::LoopDetected),
+            510 => Ok(StatusCode::NotExtended),
+            511 => Ok(StatusCode::NetworkAuthenticationRequired),
+            _ => Err(Errors::UnknownStatusCode(code)),
+        }
+    }
+}
+
+impl fmt::Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_u16())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reason_phrase() {
+        assert_eq!(StatusCode::Ok.reason_phrase(), "OK");
+        assert_eq!(
+            StatusCode::MovedPermanently.reason_phrase(),
+            "Moved Permanently"
+        );
+        assert_eq!(
+            StatusCode::ContentTooLarge.reason_phrase(),
+            "Content Too Large"
+        );
+    }
+
+    #[test]
+    fn test_try_from_u16() {
+        assert_eq!(
+            StatusCode::try_from(301).unwrap(),
+            StatusCode::MovedPermanently
+        );
+        assert_eq!(
+            StatusCode::try_from(511).unwrap(),
+            StatusCode::NetworkAuthenticationRequired
+        );
+    }
+
+    #[test]
+    fn test_try_from_u16_unknown() {
+        let err = StatusCode::try_from(299).unwrap_err();
+
+        assert!(matches!(err, Errors::UnknownStatusCode(299)));
+    }
+
+    #[test]
+    fn test_try_from_round_trip() {
+        for code in 100..600 {
+            if let Ok(status) = StatusCode::try_from(code) {
+                assert_eq!(status.as_u16(), code);
+            }
+        }
+    }
+
+    #[test]
+    fn test_classes() {
+        assert!(StatusCode::EarlyHints.is_informational());
+        assert!(StatusCode::BadGateway.is_server_error());
+
+        assert!(!StatusCode::Ok.is_informational());
+        assert!(!StatusCode::NotFound.is_server_error());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(StatusCode::Created.to_string(), "201");
+    }
+}
@@ -1,20 +1,67 @@
 use std::fmt;
 
+use crate::errors::Errors;
+use crate::http::percent_encoding::{decode_unreserved, percent_decode};
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct UriPath {
     path: String,
     data: String,
+    // Query string tal cual llegó, sin el "?"
+    raw_query: Option<String>,
+    // Request target "*" (forma asterisco), solo válido con OPTIONS
+    asterisk: bool,
+    // La ruta termina en "/" ("/files/"), `data` no lo refleja si está
+    // vacío
+    trailing_slash: bool,
 }
 
 impl UriPath {
-    pub fn new(path: &str) -> Self {
+    /// Crea la ruta a partir del request target de la solicitud.
+    ///
+    /// La ruta se normaliza (ver `normalize_path`) pero se guarda sin
+    /// decodificar. Falla si algún segmento decodificado no es UTF-8
+    /// válido o contiene un NUL.
+    pub fn new(uri: &str) -> Result<Self, Errors> {
+        if uri == "*" {
+            return Ok(UriPath {
+                asterisk: true,
+                ..UriPath::new("/")?
+            });
+        }
+
+        let (path, raw_query) = match uri.split_once('?') {
+            Some((path, query)) => (path, Some(query.to_string())),
+            None => (uri, None),
+        };
+
+        // La ruta queda sin decodificar, así un "%2F" no se confunde con
+        // un separador. Cada parámetro se decodifica al pedirlo.
+        let path = normalize_path(without_authority(path));
+
+        let valid =
+            |segment: &str| match String::from_utf8(percent_decode(segment)) {
+                Ok(decoded) => !decoded.contains('\0'),
+                Err(_) => false,
+            };
+        if !path.split('/').all(valid) {
+            return Err(Errors::InvalidUri(uri.to_string()));
+        }
+
+        let trailing_slash = path.len() > 1 && path.ends_with('/');
         let mut iter = path.splitn(3, '/');
 
         let _ = iter.next().unwrap_or("").to_string();
         let path = iter.next().unwrap_or("").to_string();
         let data = iter.next().unwrap_or("").to_string();
 
-        UriPath { path, data }
+        Ok(UriPath {
+            path,
+            data,
+            raw_query,
+            asterisk: false,
+            trailing_slash,
+        })
     }
 
     //  function name starts_with is more semantic
@@ -26,21 +73,93 @@ impl UriPath {
         format!("/{}", self.path)
     }
 
-    pub fn data(&self) -> &str {
-        &self.data
+    /// Indica si el request target es "*" (`OPTIONS * HTTP/1.1`).
+    pub fn is_asterisk(&self) -> bool {
+        self.asterisk
+    }
+
+    /// Ruta completa normalizada y sin decodificar, sin el query string.
+    pub fn full_path(&self) -> String {
+        if self.data.is_empty() && self.trailing_slash {
+            return format!("{}/", self.path());
+        }
+        if self.data.is_empty() {
+            return self.path();
+        }
+
+        format!("{}/{}", self.path(), self.data)
+    }
+
+    /// Query string sin decodificar, `None` si la URI no tiene "?".
+    pub fn raw_query(&self) -> Option<&str> {
+        self.raw_query.as_deref()
     }
 }
 
 impl fmt::Display for UriPath {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let data = if self.data.is_empty() {
-            "".to_string()
-        } else {
-            format!("/{}", self.data)
-        };
+        if self.asterisk {
+            return write!(f, "*");
+        }
+
+        write!(f, "{}", self.full_path())?;
+
+        match &self.raw_query {
+            Some(query) => write!(f, "?{}", query),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Quita el esquema y el host de un request target en forma absoluta
+/// ("http://host/ruta"), dejando solo la ruta.
+fn without_authority(path: &str) -> &str {
+    let scheme_end = ["http://", "https://"]
+        .iter()
+        .find(|scheme| {
+            path.get(..scheme.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(scheme))
+        })
+        .map(|scheme| scheme.len());
+
+    match scheme_end {
+        Some(start) => match path[start..].find('/') {
+            Some(end) => &path[start + end..],
+            None => "/",
+        },
+        None => path,
+    }
+}
+
+/// Normaliza una ruta sin decodificar.
+///
+/// Decodifica los caracteres no reservados, colapsa las barras
+/// repetidas y resuelve los segmentos "." y ".." (RFC 3986, secciones
+/// 6.2.2 y 5.2.4), así "%2E%2E" también cuenta como "..". Un ".." en la
+/// raíz se descarta. La ruta resultante siempre empieza con "/" y
+/// conserva la barra final.
+fn normalize_path(path: &str) -> String {
+    let mut segments: Vec<String> = Vec::new();
+    let mut trailing_slash = false;
 
-        write!(f, "{}{}", self.path(), data,)
+    for segment in path.split('/').map(decode_unreserved) {
+        trailing_slash = matches!(segment.as_str(), "" | "." | "..");
+
+        match segment.as_str() {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+
+    let mut result = format!("/{}", segments.join("/"));
+    if trailing_slash && !segments.is_empty() {
+        result.push('/');
     }
+
+    result
 }
 
 #[cfg(test)]
@@ -50,40 +169,175 @@ mod tests {
     #[test]
     fn test_root_path() {
         let url = "/";
-        let path = UriPath::new(url);
+        let path = UriPath::new(url).unwrap();
 
         assert_eq!(path.path(), "/");
-        assert_eq!(path.data(), "");
+        assert_eq!(path.full_path(), "/");
         assert_eq!(path.to_string(), url);
     }
 
     #[test]
     fn test_complex_path() {
         let url = "/echo/linux";
-        let path = UriPath::new(url);
+        let path = UriPath::new(url).unwrap();
 
         assert_eq!(path.path(), "/echo");
-        assert_eq!(path.data(), "linux");
+        assert_eq!(path.full_path(), "/echo/linux");
         assert_eq!(path.to_string(), url);
     }
 
     #[test]
     fn test_complex_path_with_slashes() {
         let url = "/echo/monkey/Coo-donkey";
-        let path = UriPath::new(url);
+        let path = UriPath::new(url).unwrap();
 
         assert_eq!(path.path(), "/echo");
-        assert_eq!(path.data(), "monkey/Coo-donkey");
+        assert_eq!(path.full_path(), "/echo/monkey/Coo-donkey");
         assert_eq!(path.to_string(), url);
     }
 
     #[test]
     fn test_complex_path_with_slashes_1() {
         let url = "/echo/Coo/dooby";
-        let path = UriPath::new(url);
+        let path = UriPath::new(url).unwrap();
 
         assert_eq!(path.path(), "/echo");
-        assert_eq!(path.data(), "Coo/dooby");
+        assert_eq!(path.full_path(), "/echo/Coo/dooby");
+        assert_eq!(path.to_string(), url);
+    }
+
+    #[test]
+    fn test_trailing_slash() {
+        let cases = [
+            ("/files/", "/files", "/files/"),
+            ("/files", "/files", "/files"),
+            ("/files/sub/", "/files", "/files/sub/"),
+            ("//", "/", "/"),
+        ];
+
+        for (url, path, full_path) in cases {
+            let uri = UriPath::new(url).unwrap();
+
+            assert_eq!(uri.path(), path, "{}", url);
+            assert_eq!(uri.full_path(), full_path, "{}", url);
+        }
+    }
+
+    #[test]
+    fn test_path_with_query() {
+        let url = "/echo/abc?x=1&y=hello+world&x=%32";
+        let path = UriPath::new(url).unwrap();
+
+        assert_eq!(path.path(), "/echo");
+        assert_eq!(path.full_path(), "/echo/abc");
+        assert_eq!(path.raw_query(), Some("x=1&y=hello+world&x=%32"));
+        assert_eq!(path.to_string(), url);
+    }
+
+    #[test]
+    fn test_path_with_empty_query() {
+        let url = "/?";
+        let path = UriPath::new(url).unwrap();
+
+        assert_eq!(path.path(), "/");
+        assert_eq!(path.raw_query(), Some(""));
         assert_eq!(path.to_string(), url);
     }
+
+    #[test]
+    fn test_path_without_query() {
+        let path = UriPath::new("/files/a.txt").unwrap();
+
+        assert_eq!(path.raw_query(), None);
+    }
+
+    // ---- -- Decoding and normalization -- ---- \\
+
+    fn decoded(path: &UriPath) -> String {
+        String::from_utf8(percent_decode(&path.full_path())).unwrap()
+    }
+
+    #[test]
+    fn test_percent_decoded_path() {
+        let url = "/files/my%20report.pdf";
+        let path = UriPath::new(url).unwrap();
+
+        assert_eq!(path.path(), "/files");
+        assert_eq!(decoded(&path), "/files/my report.pdf");
+        assert_eq!(path.to_string(), url);
+    }
+
+    #[test]
+    fn test_encoded_slash_is_decoded() {
+        let path = UriPath::new("/echo/a%2Fb").unwrap();
+
+        assert_eq!(path.path(), "/echo");
+        assert_eq!(path.full_path(), "/echo/a%2Fb");
+        assert_eq!(decoded(&path), "/echo/a/b");
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        let cases = [
+            ("", "/"),
+            ("/", "/"),
+            ("//files///a.txt", "/files/a.txt"),
+            ("/files/./a.txt", "/files/a.txt"),
+            ("/files/dir/../a.txt", "/files/a.txt"),
+            ("/files/../../../etc/passwd", "/etc/passwd"),
+            ("/..", "/"),
+            ("/files/dir/", "/files/dir/"),
+            ("/files/dir/.", "/files/dir/"),
+            ("/files/dir/sub/..", "/files/dir/"),
+            ("/%66iles/%2E/a%2Fb", "/files/a%2Fb"),
+            ("/files/dir/%2e%2E", "/files/"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(normalize_path(input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_encoded_dot_segments_are_removed() {
+        let path = UriPath::new("/files/%2e%2e/%2E%2E/secret.txt").unwrap();
+
+        assert_eq!(path.to_string(), "/secret.txt");
+    }
+
+    #[test]
+    fn test_encoded_slash_is_not_a_separator() {
+        let path = UriPath::new("/files/..%2F..%2Fsecret.txt").unwrap();
+
+        assert_eq!(path.path(), "/files");
+        assert_eq!(path.full_path(), "/files/..%2F..%2Fsecret.txt");
+        assert_eq!(decoded(&path), "/files/../../secret.txt");
+    }
+
+    #[test]
+    fn test_absolute_form() {
+        let path = UriPath::new("http://localhost:4221/echo/abc?x=1").unwrap();
+
+        assert_eq!(path.path(), "/echo");
+        assert_eq!(path.full_path(), "/echo/abc");
+        assert_eq!(path.raw_query(), Some("x=1"));
+    }
+
+    #[test]
+    fn test_invalid_utf8_and_nul() {
+        for url in ["/files/%ff%fe", "/files/a%00.txt"] {
+            let err = UriPath::new(url).unwrap_err();
+
+            assert!(matches!(err, Errors::InvalidUri(_)), "url: {}", url);
+        }
+    }
+
+    #[test]
+    fn test_asterisk_form() {
+        let path = UriPath::new("*").unwrap();
+
+        assert!(path.is_asterisk());
+        assert!(!UriPath::new("/*").unwrap().is_asterisk());
+        assert_eq!(path.to_string(), "*");
+    }
 }
@@ -1,5 +1,12 @@
-mod errors;
+pub mod app_state;
+pub mod autoindex;
+pub mod config;
+pub mod errors;
+pub mod file_root;
+pub mod middleware;
+pub mod mime;
 pub mod parse_url;
+pub mod path_lock;
 pub mod response_handler;
 pub mod router;
 
@@ -1,78 +1,141 @@
-use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use tokio::io::AsyncReadExt;
 use tokio::net::{TcpListener, TcpStream};
 
+mod app_state;
+mod autoindex;
+mod config;
 mod errors;
+mod file_root;
 mod http;
+mod middleware;
+mod mime;
 mod parse_url;
+mod path_lock;
 mod response_handler;
 mod router;
 
-use http::HttpRequest;
+use app_state::AppState;
+use config::Config;
+use http::{HttpRequest, RequestReader};
+use middleware::{Compression, Logger};
 use parse_url::ParseUrl;
 use router::Router;
 
-const BUFFER_SIZE: usize = 1024 * 8;
+// Tiempo máximo que una conexión puede quedar inactiva esperando
+// la siguiente solicitud antes de ser cerrada por el servidor.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Tiempo máximo entre dos lecturas de una solicitud que ya empezó, si
+// se supera se responde 408 y se cierra la conexión.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+// Cantidad máxima de solicitudes atendidas por una misma conexión.
+const MAX_REQUESTS_PER_CONNECTION: usize = 100;
 
 async fn handle_client(
-    mut stream: TcpStream,
-    path_dir: PathBuf,
+    stream: TcpStream,
+    router: Arc<Router>,
+    state: Arc<AppState>,
 ) -> Result<(), String> {
-    let mut buffer = vec![0; BUFFER_SIZE];
+    // Los bytes sobrantes de cada lectura quedan en el reader, así las
+    // solicitudes enviadas en pipeline se responden una a una y en orden.
+    let mut reader = RequestReader::new(stream, IDLE_TIMEOUT, REQUEST_TIMEOUT);
+    let mut requests_served = 0;
 
-    match stream.read(&mut buffer).await {
-        Ok(bytes_read) => {
-            let request = HttpRequest::from(&buffer[0..bytes_read]);
+    loop {
+        let request = match reader.read_request().await {
+            Ok(Some(request)) => HttpRequest::try_from(request.as_slice()),
+            // El cliente cerró la conexión o no envió nada a tiempo
+            Ok(None) => break,
+            Err(e) => Err(e),
+        };
 
-            Router::route(request, &mut stream, path_dir).await;
-        }
-        Err(e) => {
-            return Err(format!("Failed to read data:{}", e));
-        }
-    };
+        let request = match request {
+            Ok(request) => request,
+            Err(e) => {
+                if let Some(status_code) = e.status_code() {
+                    let mut response =
+                        response_handler::error_response(status_code, &e);
+                    response.add_header("Connection", "close");
 
-    Ok(())
-}
+                    let _ = response.send_response(reader.get_mut()).await;
+                }
 
-fn parse_args(args: Vec<String>) -> PathBuf {
-    if args.len() < 2 {
-        return PathBuf::from(".");
-    }
+                return Err(e.to_string());
+            }
+        };
+        requests_served += 1;
 
-    let _bin_dir = &args[0];
-    let arg_flag = &args[1];
-    let arg_dir = &args[2];
+        let version = request.version().clone();
+        let mut keep_alive = request.keep_alive()
+            && requests_served < MAX_REQUESTS_PER_CONNECTION;
 
-    if arg_flag != "--directory" {
-        panic!("Expected --directory argument");
-    }
+        let mut response = router.route(request, Arc::clone(&state)).await;
+        response.set_version(&version);
 
-    // convertir file en Path
-    let arg_dir =
-        PathBuf::from_str(arg_dir).expect("Failed to parse file path");
+        // Sin chunked, el cliente HTTP/1.0 sabe que el body terminó
+        // cuando se cierra la conexión
+        keep_alive &= !response.is_close_delimited();
 
-    if !arg_dir.is_dir() {
-        panic!("Expected directory path");
+        let connection = if keep_alive { "keep-alive" } else { "close" };
+        response.add_header("Connection", connection);
+
+        if let Err(e) = response.send_response(reader.get_mut()).await {
+            return Err(format!("Failed to send response:{}", e));
+        }
+
+        if !keep_alive {
+            break;
+        }
     }
 
-    arg_dir
+    Ok(())
+}
+
+/// Registra las rutas y los middleware del servidor.
+fn build_router(config: &Config) -> Router {
+    let mut router = Router::new();
+    router.middleware(Logger);
+    router.middleware(Compression::new(config.compress_min_size));
+
+    router.get("/", response_handler::path_default);
+    router.get("/echo/*text", response_handler::echo);
+    router.get("/user-agent", response_handler::user_agent);
+    router.post("/", response_handler::path_default);
+
+    router.group("/files", |files| {
+        files.get("/*path", response_handler::get_file);
+
+        // Sin las rutas que modifican archivos, POST, PUT o DELETE
+        // responden 405 con el mismo `Allow` que anuncia OPTIONS
+        if !config.read_only {
+            files.post("/*path", response_handler::post_file);
+            files.put("/*path", response_handler::put_file);
+            files.delete("/*path", response_handler::delete_file);
+        }
+    });
+
+    router
 }
 
 #[tokio::main]
 async fn main() {
     // Read the --directory <directory> argument
     let args: Vec<String> = std::env::args().collect();
-    let directory = parse_args(args);
-    let directory = Arc::new(directory);
+    let config = Config::from_args(args);
+
+    let state = AppState::new(config).expect("Failed to open the directory");
 
-    println!("Directory: {:?}", &directory);
+    println!("Directory: {:?}", state.file_root.root());
+    let state = Arc::new(state);
+    let router = Arc::new(build_router(&state.config));
     println!("Server is starting...");
 
-    let url = "127.0.0.1:4221/";
-    let url = ParseUrl::from_str(url).unwrap();
+    let url = format!("127.0.0.1:{}/", state.config.port);
+    let url = ParseUrl::from_str(&url).unwrap();
 
     let listener = match TcpListener::bind(url.get_host()).await {
         Ok(listener) => listener,
@@ -83,7 +146,8 @@ async fn main() {
     };
 
     loop {
-        let directory = Arc::clone(&directory);
+        let router = Arc::clone(&router);
+        let state = Arc::clone(&state);
 
         let (stream, addr) = match listener.accept().await {
             Ok((stream, addr)) => (stream, addr),
@@ -97,10 +161,7 @@ async fn main() {
         println!("Accepting connection from {}", addr);
 
         tokio::spawn(async move {
-            let directory = Arc::clone(&directory);
-
-            if let Err(e) = handle_client(stream, directory.to_path_buf()).await
-            {
+            if let Err(e) = handle_client(stream, router, state).await {
                 println!("Connection with {} failed: {}", addr, e);
             }
         });
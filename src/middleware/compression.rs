@@ -0,0 +1,246 @@
+use std::sync::Arc;
+
+use crate::app_state::AppState;
+use crate::http::{
+    ContentCoding, EntityTag, HttpRequest, HttpResponse, StatusCode,
+};
+use crate::middleware::{Middleware, MiddlewareFuture, Next};
+
+// Tipos de contenido que ya están comprimidos, comprimirlos de nuevo
+// solo gasta CPU.
+const COMPRESSED_TYPES: [&str; 10] = [
+    "application/gzip",
+    "application/x-gzip",
+    "application/zip",
+    "application/x-bzip2",
+    "application/x-xz",
+    "application/x-7z-compressed",
+    "application/x-rar-compressed",
+    "application/zstd",
+    "font/woff",
+    "font/woff2",
+];
+
+/// Comprime las respuestas con la codificación que el cliente prefiere
+/// según `Accept-Encoding`.
+///
+/// No se comprimen las respuestas sin body o más chicas que `min_size`,
+/// las que ya tienen `Content-Encoding`, las parciales (206) ni los
+/// tipos de contenido que ya vienen comprimidos.
+pub struct Compression {
+    min_size: u64,
+}
+
+impl Compression {
+    pub fn new(min_size: u64) -> Self {
+        Compression { min_size }
+    }
+
+    fn applies_to(&self, response: &HttpResponse) -> bool {
+        let status_code = response.status_code();
+        let headers = response.headers();
+
+        let big_enough = match response.body().length() {
+            Some(length) => length > 0 && length >= self.min_size,
+            // Los streams de longitud desconocida se comprimen siempre
+            None => true,
+        };
+
+        status_code.allows_body()
+            && status_code != StatusCode::PartialContent
+            && !headers.contains_key("Content-Encoding")
+            && is_compressible(headers.content_type())
+            && big_enough
+    }
+}
+
+impl Middleware for Compression {
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        state: Arc<AppState>,
+        next: Next<'a>,
+    ) -> MiddlewareFuture<'a> {
+        let coding = ContentCoding::negotiate(&request.headers);
+
+        Box::pin(async move {
+            let mut response = next.run(request, state).await;
+
+            if !self.applies_to(&response) {
+                return response;
+            }
+
+            // La respuesta depende de `Accept-Encoding` aunque esta vez
+            // no se comprima
+            let varies = response.headers().get_list("Vary").any(|name| {
+                name == "*" || name.eq_ignore_ascii_case("Accept-Encoding")
+            });
+            if !varies {
+                response.headers_mut().append("Vary", "Accept-Encoding");
+            }
+
+            if coding == ContentCoding::Identity {
+                return response;
+            }
+
+            let body = coding.encode_body(response.take_body());
+            response.set_body(body);
+            response.add_header("Content-Encoding", coding.as_str());
+
+            // Los bytes comprimidos son otra representación, con su
+            // propio ETag fuerte
+            let etag =
+                response.headers().get("ETag").and_then(EntityTag::parse);
+            if let Some(etag) = etag {
+                response
+                    .add_header("ETag", &etag.with_coding(coding).to_string());
+            }
+
+            response
+        })
+    }
+}
+
+/// Indica si vale la pena comprimir un body de tipo `content_type`.
+fn is_compressible(content_type: Option<&str>) -> bool {
+    let Some(content_type) = content_type else {
+        return true;
+    };
+
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+
+    if mime == "image/svg+xml" {
+        return true;
+    }
+
+    let media = ["image/", "audio/", "video/"];
+    if media.iter().any(|prefix| mime.starts_with(prefix)) {
+        return false;
+    }
+
+    !COMPRESSED_TYPES.contains(&mime.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use crate::config::Config;
+    use crate::http::{Body, HeaderMap};
+    use crate::router::Router;
+
+    async fn route(router: &Router, request: &str) -> HttpResponse {
+        let request = HttpRequest::try_from(request.as_bytes()).unwrap();
+        let state = AppState::new(Config::default()).unwrap();
+
+        router.route(request, Arc::new(state)).await
+    }
+
+    /// Router con una ruta que responde `size` bytes de tipo `mime`.
+    fn router(min_size: u64) -> Router {
+        let mut router = Router::new();
+        router.middleware(Compression::new(min_size));
+
+        router.get("/:mime/:size", |request: HttpRequest, _state| async move {
+            let mime = request.param("mime").unwrap().replace('_', "/");
+            let size: usize = request.param("size").unwrap().parse().unwrap();
+            let headers = HeaderMap::from([
+                ("Content-Type", mime.as_str()),
+                ("ETag", "\"abc\""),
+            ]);
+
+            HttpResponse::new(
+                StatusCode::Ok,
+                Some(headers),
+                Some(&vec![b'a'; size]),
+            )
+        });
+
+        router
+    }
+
+    #[tokio::test]
+    async fn test_compression_gzip() {
+        let request =
+            "GET /text_plain/100 HTTP/1.1\r\nAccept-Encoding: gzip\r\n\r\n";
+        let response = route(&router(0), request).await;
+
+        assert_eq!(response.headers().get("Content-Encoding"), Some("gzip"));
+        assert_eq!(response.headers().get("Vary"), Some("Accept-Encoding"));
+        assert_eq!(response.headers().get("ETag"), Some("\"abc-gzip\""));
+
+        let Body::Full(bytes) = response.body() else {
+            panic!("Expected a full body");
+        };
+        let mut body = Vec::new();
+        GzDecoder::new(&bytes[..]).read_to_end(&mut body).unwrap();
+
+        assert_eq!(body, vec![b'a'; 100]);
+    }
+
+    #[tokio::test]
+    async fn test_compression_not_accepted() {
+        let cases = [
+            "",
+            "Accept-Encoding: gzip;q=0\r\n",
+            "Accept-Encoding: br\r\n",
+        ];
+
+        for accept_encoding in cases {
+            let request = format!(
+                "GET /text_plain/100 HTTP/1.1\r\n{}\r\n",
+                accept_encoding
+            );
+            let response = route(&router(0), &request).await;
+
+            assert_eq!(response.headers().get("Content-Encoding"), None);
+            assert_eq!(response.headers().get("Vary"), Some("Accept-Encoding"));
+            assert_eq!(response.headers().get("ETag"), Some("\"abc\""));
+            assert_eq!(response.body().length(), Some(100));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compression_skipped() {
+        let cases = [
+            ("/text_plain/100", 1024),
+            ("/text_plain/0", 0),
+            ("/image_png/100", 0),
+            ("/application_zip/100", 0),
+        ];
+
+        for (path, min_size) in cases {
+            let request = format!(
+                "GET {} HTTP/1.1\r\nAccept-Encoding: gzip\r\n\r\n",
+                path
+            );
+            let response = route(&router(min_size), &request).await;
+
+            assert_eq!(
+                response.headers().get("Content-Encoding"),
+                None,
+                "{}",
+                path
+            );
+            assert_eq!(response.headers().get("Vary"), None, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_is_compressible() {
+        assert!(is_compressible(None));
+        assert!(is_compressible(Some("text/html; charset=utf-8")));
+        assert!(is_compressible(Some("image/svg+xml")));
+        assert!(!is_compressible(Some("Image/JPEG")));
+        assert!(!is_compressible(Some("application/gzip")));
+    }
+}
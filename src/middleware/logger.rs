@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use crate::app_state::AppState;
+use crate::http::HttpRequest;
+use crate::middleware::{Middleware, MiddlewareFuture, Next};
+
+/// Muestra por consola cada solicitud junto con el estado de su respuesta.
+///
+/// Las respuestas 5xx van a la salida de errores, así se pueden separar
+/// de las demás sin filtrar el log.
+pub struct Logger;
+
+impl Middleware for Logger {
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        state: Arc<AppState>,
+        next: Next<'a>,
+    ) -> MiddlewareFuture<'a> {
+        let method = request.method().as_str().to_string();
+        let uri = request.uri().to_string();
+
+        Box::pin(async move {
+            let response = next.run(request, state).await;
+            let status_code = response.status_code();
+
+            if status_code.is_server_error() {
+                eprintln!("{} {} -> {}", method, uri, status_code);
+            } else {
+                println!("{} {} -> {}", method, uri, status_code);
+            }
+
+            response
+        })
+    }
+}
@@ -0,0 +1,98 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::app_state::AppState;
+use crate::http::{HttpRequest, HttpResponse};
+use crate::response_handler::Handler;
+use crate::router::Router;
+
+mod compression;
+pub use compression::Compression;
+
+mod logger;
+pub use logger::Logger;
+
+// Respuesta que produce un `Middleware`, puede tomar prestado el
+// middleware y el resto de la cadena.
+pub type MiddlewareFuture<'a> =
+    Pin<Box<dyn Future<Output = HttpResponse> + Send + 'a>>;
+
+// ---- -- Middleware -- ---- \\
+
+/// Capa que envuelve el procesamiento de una solicitud.
+///
+/// Puede modificar la solicitud antes de llamar a `next.run`, responder
+/// sin llamarlo (cortando la cadena) o modificar la respuesta que
+/// devuelve `next.run`.
+pub trait Middleware: Send + Sync + 'static {
+    fn handle<'a>(
+        &'a self,
+        request: HttpRequest,
+        state: Arc<AppState>,
+        next: Next<'a>,
+    ) -> MiddlewareFuture<'a>;
+}
+
+// ---- -- Next -- ---- \\
+
+// Lo que se ejecuta al final de la cadena.
+enum Endpoint<'a> {
+    // Los middleware globales terminan en el router, que elige la ruta
+    Router(&'a Router),
+    // Los middleware de un grupo terminan en el handler de la ruta
+    Handler(&'a dyn Handler),
+}
+
+/// Resto de la cadena de middleware a partir del actual.
+pub struct Next<'a> {
+    middlewares: &'a [Arc<dyn Middleware>],
+    endpoint: Endpoint<'a>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn router(
+        middlewares: &'a [Arc<dyn Middleware>],
+        router: &'a Router,
+    ) -> Self {
+        Next {
+            middlewares,
+            endpoint: Endpoint::Router(router),
+        }
+    }
+
+    pub(crate) fn handler(
+        middlewares: &'a [Arc<dyn Middleware>],
+        handler: &'a dyn Handler,
+    ) -> Self {
+        Next {
+            middlewares,
+            endpoint: Endpoint::Handler(handler),
+        }
+    }
+
+    /// Ejecuta el siguiente middleware, o el final de la cadena si ya
+    /// no quedan.
+    pub fn run(
+        self,
+        request: HttpRequest,
+        state: Arc<AppState>,
+    ) -> MiddlewareFuture<'a> {
+        match self.middlewares.split_first() {
+            Some((middleware, rest)) => {
+                let next = Next {
+                    middlewares: rest,
+                    endpoint: self.endpoint,
+                };
+
+                middleware.handle(request, state, next)
+            }
+            None => match self.endpoint {
+                Endpoint::Router(router) => {
+                    Box::pin(router.dispatch(request, state))
+                }
+                Endpoint::Handler(handler) => handler.call(request, state),
+            },
+        }
+    }
+}
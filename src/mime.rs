@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+// Cantidad de bytes del inicio de un archivo que se usan para adivinar
+// su tipo cuando la extensión no es conocida.
+pub const SNIFF_LENGTH: usize = 512;
+
+// Tipo de los archivos que no se pudieron identificar.
+pub const DEFAULT_MIME_TYPE: &str = "application/octet-stream";
+
+// Tipos conocidos por extensión, se pueden reemplazar desde la
+// configuración con `--mime-type <ext>=<tipo>`.
+const MIME_TYPES: [(&str, &str); 30] = [
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("txt", "text/plain"),
+    ("md", "text/markdown"),
+    ("csv", "text/csv"),
+    ("xml", "application/xml"),
+    ("json", "application/json"),
+    ("wasm", "application/wasm"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("svg", "image/svg+xml"),
+    ("ico", "image/x-icon"),
+    ("mp3", "audio/mpeg"),
+    ("wav", "audio/wav"),
+    ("ogg", "audio/ogg"),
+    ("mp4", "video/mp4"),
+    ("webm", "video/webm"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("ttf", "font/ttf"),
+];
+
+// Tipos genéricos que un cliente puede enviar al subir cualquier
+// archivo, `curl -d` usa "application/x-www-form-urlencoded" por defecto.
+const GENERIC_UPLOAD_TYPES: [&str; 2] = [
+    "application/octet-stream",
+    "application/x-www-form-urlencoded",
+];
+
+// ---- -- Mime Registry -- ---- \\
+
+/// Tipos MIME de los archivos que sirve el servidor.
+///
+/// El tipo se busca por la extensión del archivo, y si no es conocida
+/// (y `sniff` está activo) se adivina a partir de su contenido.
+#[derive(Debug, Clone)]
+pub struct MimeRegistry {
+    types: HashMap<String, String>,
+    sniff: bool,
+}
+
+impl Default for MimeRegistry {
+    fn default() -> Self {
+        MimeRegistry::new(true)
+    }
+}
+
+impl MimeRegistry {
+    pub fn new(sniff: bool) -> Self {
+        let types = MIME_TYPES
+            .iter()
+            .map(|(extension, mime)| (extension.to_string(), mime.to_string()))
+            .collect();
+
+        MimeRegistry { types, sniff }
+    }
+
+    /// Registra (o reemplaza) el tipo de la extensión `extension`.
+    pub fn insert(&mut self, extension: &str, mime: &str) {
+        let extension = extension.trim_start_matches('.').to_lowercase();
+
+        self.types.insert(extension, mime.to_string());
+    }
+
+    /// Tipo que corresponde a la extensión de `path`, sin distinguir
+    /// mayúsculas.
+    pub fn by_extension(&self, path: &Path) -> Option<&str> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        self.types.get(&extension).map(|mime| mime.as_str())
+    }
+
+    /// Indica si para conocer el tipo de `path` hace falta leer el
+    /// inicio del archivo.
+    pub fn needs_sniffing(&self, path: &Path) -> bool {
+        self.sniff && self.by_extension(path).is_none()
+    }
+
+    /// Valor de `Content-Type` para el archivo `path`, `head` son los
+    /// primeros bytes del archivo (puede estar vacío si no hacen falta).
+    ///
+    /// Los tipos de texto se envían con `charset=utf-8`.
+    pub fn content_type(&self, path: &Path, head: &[u8]) -> String {
+        let sniffed = || match self.sniff {
+            true => sniff(head),
+            false => None,
+        };
+
+        let mime = self
+            .by_extension(path)
+            .or_else(sniffed)
+            .unwrap_or(DEFAULT_MIME_TYPE);
+
+        if mime.starts_with("text/") && !mime.contains("charset") {
+            return format!("{}; charset=utf-8", mime);
+        }
+
+        mime.to_string()
+    }
+
+    /// Indica si un archivo subido a `path` puede declarar el tipo
+    /// `content_type`.
+    ///
+    /// Se aceptan los tipos genéricos, las extensiones desconocidas y
+    /// el tipo que corresponde a la extensión.
+    pub fn accepts_upload(&self, path: &Path, content_type: &str) -> bool {
+        let declared = essence(content_type);
+
+        if GENERIC_UPLOAD_TYPES.contains(&declared.as_str()) {
+            return true;
+        }
+
+        match self.by_extension(path) {
+            Some(expected) => essence(expected) == declared,
+            None => true,
+        }
+    }
+}
+
+/// Tipo sin parámetros y en minúsculas ("Text/HTML; charset=utf-8"
+/// pasa a ser "text/html").
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Adivina el tipo de un archivo a partir de sus primeros bytes.
+///
+/// Reconoce las firmas de los formatos binarios más comunes y los
+/// documentos HTML, cualquier otro contenido UTF-8 sin caracteres de
+/// control se considera texto plano.
+pub fn sniff(head: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: [(&[u8], &str); 8] = [
+        (b"\x89PNG\r\n\x1a\n", "image/png"),
+        (b"\xff\xd8\xff", "image/jpeg"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1f\x8b", "application/gzip"),
+        (b"\x00asm", "application/wasm"),
+    ];
+
+    if head.is_empty() {
+        return None;
+    }
+
+    if let Some((_, mime)) = SIGNATURES
+        .iter()
+        .find(|(signature, _)| head.starts_with(signature))
+    {
+        return Some(mime);
+    }
+
+    if head.len() >= 12 && &head[..4] == b"RIFF" && &head[8..12] == b"WEBP" {
+        return Some("image/webp");
+    }
+
+    // El archivo puede cortarse a mitad de un carácter UTF-8
+    let text = match std::str::from_utf8(head) {
+        Ok(text) => text,
+        Err(e) if e.error_len().is_none() => {
+            std::str::from_utf8(&head[..e.valid_up_to()]).unwrap_or("")
+        }
+        Err(_) => return None,
+    };
+
+    let start = text.trim_start().get(..14).unwrap_or("").to_lowercase();
+    if start.starts_with("<!doctype html") || start.starts_with("<html") {
+        return Some("text/html");
+    }
+
+    let is_binary = text
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\t' | '\n' | '\r' | '\x0c'));
+
+    if is_binary {
+        None
+    } else {
+        Some("text/plain")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_by_extension() {
+        let registry = MimeRegistry::default();
+
+        let cases = [
+            ("index.html", Some("text/html")),
+            ("dir/Rick_and_Morty.JPG", Some("image/jpeg")),
+            ("archive.tar.gz", Some("application/gzip")),
+            ("README", None),
+            ("file.unknown", None),
+        ];
+
+        for (path, expected) in cases {
+            assert_eq!(registry.by_extension(Path::new(path)), expected);
+        }
+    }
+
+    #[test]
+    fn test_content_type() {
+        let registry = MimeRegistry::default();
+
+        let cases = [
+            ("index.html", &b""[..], "text/html; charset=utf-8"),
+            ("photo.jpg", b"", "image/jpeg"),
+            (
+                "README",
+                b"Wubba Lubba Dub Dub\n",
+                "text/plain; charset=utf-8",
+            ),
+            (
+                "page",
+                b"  <!DOCTYPE html><html>",
+                "text/html; charset=utf-8",
+            ),
+            ("image", b"\x89PNG\r\n\x1a\n\x00\x00", "image/png"),
+            ("data.bin", b"\x00\x01\x02\x03", DEFAULT_MIME_TYPE),
+            ("empty", b"", DEFAULT_MIME_TYPE),
+        ];
+
+        for (path, head, expected) in cases {
+            let content_type = registry.content_type(Path::new(path), head);
+
+            assert_eq!(content_type, expected, "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_content_type_without_sniffing() {
+        let registry = MimeRegistry::new(false);
+        let path = Path::new("README");
+
+        assert!(!registry.needs_sniffing(path));
+        assert_eq!(registry.content_type(path, b"text"), DEFAULT_MIME_TYPE);
+    }
+
+    #[test]
+    fn test_insert_overrides_extension() {
+        let mut registry = MimeRegistry::default();
+        registry.insert(".md", "text/plain");
+        registry.insert("RS", "text/x-rust");
+
+        let path = Path::new("main.rs");
+
+        assert!(!registry.needs_sniffing(path));
+        assert_eq!(
+            registry.content_type(Path::new("README.md"), b""),
+            "text/plain; charset=utf-8"
+        );
+        assert_eq!(
+            registry.content_type(path, b""),
+            "text/x-rust; charset=utf-8"
+        );
+    }
+
+    #[test]
+    fn test_sniff_truncated_utf8() {
+        let head = "camión".as_bytes();
+
+        assert_eq!(sniff(&head[..head.len() - 1]), Some("text/plain"));
+        assert_eq!(sniff(b"\xff\xfe\x00"), None);
+    }
+
+    #[test]
+    fn test_accepts_upload() {
+        let registry = MimeRegistry::default();
+        let html = Path::new("index.html");
+
+        assert!(registry.accepts_upload(html, "text/html"));
+        assert!(registry.accepts_upload(html, "Text/HTML; charset=utf-8"));
+        assert!(registry.accepts_upload(html, "application/octet-stream"));
+        assert!(registry.accepts_upload(Path::new("file.xyz"), "image/png"));
+        assert!(!registry.accepts_upload(html, "image/png"));
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tokio::sync::Notify;
+
+// ---- -- Path Locks -- ---- \\
+
+/// Rutas que alguna solicitud está modificando.
+///
+/// Quien sube o borra un archivo toma su ruta antes de evaluar las
+/// precondiciones y la suelta después de escribir, así dos solicitudes
+/// sobre el mismo archivo no pueden validar el mismo estado y pisarse.
+#[derive(Debug, Default)]
+pub struct PathLocks {
+    locked: Mutex<HashSet<PathBuf>>,
+    // Despierta a quienes esperan cada vez que se suelta una ruta
+    released: Notify,
+}
+
+impl PathLocks {
+    pub fn new() -> Self {
+        PathLocks::default()
+    }
+
+    /// Espera hasta que `path` esté libre y la toma, se suelta cuando
+    /// se descarta el `PathGuard`.
+    pub async fn lock(&self, path: &Path) -> PathGuard<'_> {
+        loop {
+            // Se registra antes de mirar el conjunto para no perder un
+            // aviso que llegue entre medio
+            let released = self.released.notified();
+            tokio::pin!(released);
+            released.as_mut().enable();
+
+            if self.locked.lock().unwrap().insert(path.to_path_buf()) {
+                return PathGuard {
+                    locks: self,
+                    path: path.to_path_buf(),
+                };
+            }
+
+            released.await;
+        }
+    }
+}
+
+/// Ruta tomada con `PathLocks::lock`.
+#[derive(Debug)]
+pub struct PathGuard<'a> {
+    locks: &'a PathLocks,
+    path: PathBuf,
+}
+
+impl Drop for PathGuard<'_> {
+    fn drop(&mut self) {
+        self.locks.locked.lock().unwrap().remove(&self.path);
+        self.locks.released.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_lock_same_path_waits() {
+        let locks = Arc::new(PathLocks::new());
+        let guard = locks.lock(Path::new("a.txt")).await;
+
+        // Otra ruta no espera
+        drop(locks.lock(Path::new("b.txt")).await);
+
+        let waiting = tokio::spawn({
+            let locks = locks.clone();
+            async move {
+                let _guard = locks.lock(Path::new("a.txt")).await;
+            }
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!waiting.is_finished());
+
+        drop(guard);
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
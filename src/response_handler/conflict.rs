@@ -0,0 +1,14 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 409, para operaciones que no se pueden hacer sobre el
+/// estado actual del recurso (por ejemplo escribir en un directorio).
+pub fn conflict() -> HttpResponse {
+    let status_code = StatusCode::Conflict;
+    let body = "El recurso pedido no admite esta operación en su estado actual";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
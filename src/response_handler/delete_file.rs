@@ -0,0 +1,58 @@
+use std::io::ErrorKind;
+use std::sync::Arc;
+
+use crate::app_state::AppState;
+use crate::errors::Errors;
+use crate::file_root::is_temp_file;
+use crate::http::HeaderMap;
+use crate::http::HttpRequest;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+use crate::http::{evaluate_preconditions, Precondition, Validators};
+use crate::response_handler::{
+    conflict, forbidden, internal_server_error, path_not_found,
+    precondition_failed,
+};
+
+/// Borra un archivo del directorio del servidor, los directorios no se
+/// pueden borrar.
+pub async fn delete_file(
+    request: HttpRequest,
+    state: Arc<AppState>,
+) -> HttpResponse {
+    let file_name = request.raw_param("path").unwrap_or_default();
+    let path_file = match state.file_root.resolve(file_name) {
+        // Borrar un temporal haría fallar la subida que lo está escribiendo
+        Ok(path) if !is_temp_file(&path) => path,
+        Err(Errors::PathOutsideRoot(_)) => return forbidden(),
+        _ => return path_not_found(),
+    };
+
+    // Una subida simultánea no puede cambiar el archivo entre las
+    // precondiciones y el borrado
+    let _guard = state.path_locks.lock(&path_file).await;
+
+    let metadata = match tokio::fs::metadata(&path_file).await {
+        Ok(metadata) if metadata.is_dir() => return conflict(),
+        Ok(metadata) => metadata,
+        Err(_) => return path_not_found(),
+    };
+
+    let current = Validators::from_metadata(&metadata);
+    let method = request.method();
+    match evaluate_preconditions(method, &request.headers, Some(&current)) {
+        Precondition::Passed => {}
+        _ => return precondition_failed(),
+    }
+
+    match tokio::fs::remove_file(&path_file).await {
+        // Sin body no corresponde el `Content-type` por defecto
+        Ok(()) => {
+            let headers = HeaderMap::new();
+            HttpResponse::new(StatusCode::NoContent, Some(headers), None)
+        }
+        // Otra solicitud lo borró primero
+        Err(error) if error.kind() == ErrorKind::NotFound => path_not_found(),
+        Err(_) => internal_server_error(),
+    }
+}
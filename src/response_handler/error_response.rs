@@ -0,0 +1,19 @@
+use crate::errors::Errors;
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta para una solicitud que no se pudo atender por `error`, con
+/// la descripción del error como body.
+pub fn error_response(status_code: StatusCode, error: &Errors) -> HttpResponse {
+    let body = error.to_string();
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+
+    // Indica al cliente qué codificaciones sí puede usar en el body
+    if let Errors::UnsupportedContentEncoding(_) = error {
+        headers.insert("Accept-Encoding", "gzip, deflate");
+    }
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
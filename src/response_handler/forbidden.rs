@@ -0,0 +1,13 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 403, para rutas fuera del directorio permitido.
+pub fn forbidden() -> HttpResponse {
+    let status_code = StatusCode::Forbidden;
+    let body = "No tiene permiso para acceder al recurso pedido";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
@@ -1,36 +1,362 @@
-use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
+use std::collections::VecDeque;
+use std::fs::Metadata;
+use std::io::{self, Cursor, ErrorKind, SeekFrom};
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use nom::AsBytes;
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+use tokio::io::{ReadBuf, Take};
 
+use crate::app_state::AppState;
+use crate::autoindex::{DirectoryListing, ListingFormat};
+use crate::errors::Errors;
+use crate::file_root::is_temp_file;
+use crate::http::HeaderMap;
 use crate::http::HttpRequest;
 use crate::http::HttpResponse;
-use crate::response_handler::Handler;
-use crate::response_handler::PathNotFoundHandler;
-
-pub struct GetFileHandler;
-
-impl Handler<PathBuf> for GetFileHandler {
-    fn handle(request: &HttpRequest, path_dir: PathBuf) -> HttpResponse {
-        let file_name = request.uri().data();
-        let path_file = path_dir.join(file_name);
-
-        let http_response: HttpResponse = match fs::read(path_file) {
-            Ok(file) => {
-                let status_code = "200";
-                let body = file.as_bytes();
-                let mut headers = HashMap::new();
-                headers.insert(
-                    "Content-type".to_string(),
-                    "application/octet-stream".to_string(),
-                );
-
-                HttpResponse::new(status_code, Some(headers), Some(body))
-            }
-            Err(_) => PathNotFoundHandler::handle(request, ()),
+use crate::http::StatusCode;
+use crate::http::{evaluate_preconditions, Precondition, Validators};
+use crate::http::{parse_range, ByteRange, EntityTag, HttpDate, HttpMethod};
+use crate::http::{percent_decode, percent_encode_path};
+use crate::mime::SNIFF_LENGTH;
+use crate::response_handler::{
+    forbidden, moved_permanently, not_modified, path_not_found,
+    precondition_failed, range_not_satisfiable,
+};
+
+// Archivo que se envía al pedir un directorio.
+const INDEX_FILE: &str = "index.html";
+
+/// Envía un archivo del directorio del servidor.
+///
+/// Para un directorio se envía su `index.html` o, con `--autoindex`, un
+/// listado de su contenido. Si a la ruta le falta la "/" final se
+/// redirige primero, así los enlaces relativos apuntan al directorio.
+///
+/// Las precondiciones (`If-None-Match`, `If-Match`...) se comparan con
+/// el `ETag` y `Last-Modified` del archivo y pueden responder 304 o 412.
+///
+/// Con `Range` se envían solo las partes pedidas (206), varias partes
+/// van en un body `multipart/byteranges`. Los rangos se ignoran si el
+/// archivo cambió desde el validador indicado en `If-Range`.
+pub async fn get_file(
+    request: HttpRequest,
+    state: Arc<AppState>,
+) -> HttpResponse {
+    let file_name = request.raw_param("path").unwrap_or_default();
+    let mut path_file = match state.file_root.resolve(file_name) {
+        Ok(path) if !is_temp_file(&path) => path,
+        Err(Errors::PathOutsideRoot(_)) => return forbidden(),
+        // Un temporal o un nombre con una "/" codificada, que no puede
+        // ser un archivo
+        _ => return path_not_found(),
+    };
+
+    // El archivo se envía en streaming, sin cargarlo completo en memoria
+    let Some((mut file, mut metadata)) = open(&path_file).await else {
+        return path_not_found();
+    };
+
+    if metadata.is_dir() {
+        let index_name = Path::new(file_name).join(INDEX_FILE);
+        let index = match state.file_root.resolve(&index_name.to_string_lossy())
+        {
+            Ok(index_path) => open(&index_path)
+                .await
+                .filter(|(_, metadata)| metadata.is_file())
+                .map(|(file, metadata)| (index_path, file, metadata)),
+            Err(_) => None,
         };
 
-        http_response
+        if index.is_none() && !state.config.autoindex {
+            return path_not_found();
+        }
+
+        let uri = request.uri();
+        if !uri.full_path().ends_with('/') {
+            let mut location = uri.full_path();
+            location.push('/');
+            if let Some(query) = uri.raw_query() {
+                location.push('?');
+                location.push_str(query);
+            }
+
+            return moved_permanently(&location);
+        }
+
+        match index {
+            Some(index) => (path_file, file, metadata) = index,
+            None => {
+                return list_directory(&request, &state, file_name, &path_file)
+                    .await
+            }
+        }
+    }
+
+    if !metadata.is_file() {
+        return path_not_found();
+    }
+    let length = metadata.len();
+
+    let validators = Validators::from_metadata(&metadata);
+    let method = request.method();
+    match evaluate_preconditions(method, &request.headers, Some(&validators)) {
+        Precondition::Passed => {}
+        Precondition::NotModified => return not_modified(&validators),
+        Precondition::Failed => return precondition_failed(),
+    }
+
+    // Los bytes leídos para adivinar el tipo se envían antes que el
+    // resto del archivo
+    let mut head = Vec::new();
+    if state.mime.needs_sniffing(&path_file) {
+        let mut reader = (&mut file).take(SNIFF_LENGTH as u64);
+
+        if reader.read_to_end(&mut head).await.is_err() {
+            return path_not_found();
+        }
+    }
+
+    let content_type = state.mime.content_type(&path_file, &head);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", &content_type);
+    headers.insert("Accept-Ranges", "bytes");
+    validators.insert_into(&mut headers);
+
+    // Solo GET define el manejo de rangos (RFC 9110, sección 14.2)
+    let ranges = match request.headers.get("Range") {
+        Some(range)
+            if *method == HttpMethod::Get
+                && if_range_matches(&request, &validators) =>
+        {
+            parse_range(range, length)
+        }
+        _ => None,
+    };
+
+    let response = match ranges.as_deref() {
+        None => Ok(HttpResponse::new_stream(
+            StatusCode::Ok,
+            Some(headers),
+            Cursor::new(head).chain(file),
+            Some(length),
+        )),
+        Some([]) => Ok(range_not_satisfiable(length)),
+        Some([range]) => single_range(file, headers, *range, length).await,
+        Some(ranges) => Ok(multiple_ranges(
+            file,
+            headers,
+            &content_type,
+            ranges,
+            length,
+        )),
+    };
+
+    // El archivo dejó de poder leerse mientras se preparaba la respuesta
+    response.unwrap_or_else(|_| path_not_found())
+}
+
+/// Abre `path` junto con sus metadatos, `None` si no existe o no se
+/// puede leer.
+async fn open(path: &Path) -> Option<(File, Metadata)> {
+    let file = File::open(path).await.ok()?;
+    let metadata = file.metadata().await.ok()?;
+
+    Some((file, metadata))
+}
+
+/// Listado del directorio `dir` en HTML o JSON, según `Accept`.
+///
+/// Se omiten las entradas que no se podrían pedir, como los enlaces
+/// simbólicos que apuntan fuera del directorio del servidor.
+async fn list_directory(
+    request: &HttpRequest,
+    state: &AppState,
+    file_name: &str,
+    dir: &Path,
+) -> HttpResponse {
+    let uri_path = percent_decode(&request.uri().full_path());
+    let uri_path = String::from_utf8_lossy(&uri_path);
+    let Ok(mut listing) = DirectoryListing::read(dir, &uri_path).await else {
+        return path_not_found();
+    };
+
+    listing.retain(|entry| {
+        let name = percent_encode_path(&entry.name);
+        let relative = Path::new(file_name).join(name);
+
+        state.file_root.resolve(&relative.to_string_lossy()).is_ok()
+    });
+
+    let format = ListingFormat::negotiate(&request.headers);
+    let body = listing.render(format);
+
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", format.content_type());
+    headers.insert("Vary", "Accept");
+
+    HttpResponse::new(StatusCode::Ok, Some(headers), Some(body.as_bytes()))
+}
+
+/// Indica si se deben aplicar los rangos pedidos: sin `If-Range`
+/// siempre, con `If-Range` solo si su validador corresponde al archivo.
+fn if_range_matches(request: &HttpRequest, validators: &Validators) -> bool {
+    let Some(value) = request.headers.get("If-Range") else {
+        return true;
+    };
+
+    if let Some(tag) = EntityTag::parse(value) {
+        return tag.strong_eq(&validators.etag);
+    }
+
+    match (HttpDate::parse(value), validators.last_modified) {
+        (Some(date), Some(last_modified)) => date == last_modified,
+        _ => false,
+    }
+}
+
+async fn single_range(
+    mut file: File,
+    mut headers: HeaderMap,
+    range: ByteRange,
+    length: u64,
+) -> std::io::Result<HttpResponse> {
+    file.seek(SeekFrom::Start(range.start)).await?;
+    headers.insert("Content-Range", &range.content_range(length));
+
+    Ok(HttpResponse::new_stream(
+        StatusCode::PartialContent,
+        Some(headers),
+        file.take(range.length()),
+        Some(range.length()),
+    ))
+}
+
+/// Respuesta `multipart/byteranges` (RFC 9110, sección 14.6), cada
+/// parte se lee del archivo recién al enviarla.
+fn multiple_ranges(
+    file: File,
+    mut headers: HeaderMap,
+    content_type: &str,
+    ranges: &[ByteRange],
+    length: u64,
+) -> HttpResponse {
+    let boundary = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos());
+    let boundary = format!("{:032x}", boundary);
+
+    let mut parts = VecDeque::new();
+    let mut body_length = 0;
+
+    for range in ranges {
+        let part_head = format!(
+            "\r\n--{}\r\nContent-Type: {}\r\nContent-Range: {}\r\n\r\n",
+            boundary,
+            content_type,
+            range.content_range(length)
+        );
+
+        body_length += part_head.len() as u64 + range.length();
+        parts.push_back((Cursor::new(part_head.into_bytes()), *range));
+    }
+
+    let closing = format!("\r\n--{}--\r\n", boundary);
+    body_length += closing.len() as u64;
+
+    let body = RangeParts {
+        file: file.take(0),
+        parts,
+        state: PartState::Head,
+    };
+    let body = body.chain(Cursor::new(closing.into_bytes()));
+
+    let content_type = format!("multipart/byteranges; boundary={}", boundary);
+    headers.insert("Content-type", &content_type);
+
+    HttpResponse::new_stream(
+        StatusCode::PartialContent,
+        Some(headers),
+        body,
+        Some(body_length),
+    )
+}
+
+// ---- -- Range Parts -- ---- \\
+
+// Etapa de la parte que se está enviando.
+enum PartState {
+    Head,
+    Seek,
+    Body,
+}
+
+/// Partes de un body `multipart/byteranges`, leídas todas del mismo
+/// descriptor del archivo.
+///
+/// Los rangos están ordenados y no se superponen, así que entre una
+/// parte y la siguiente el archivo solo se mueve hacia adelante.
+struct RangeParts {
+    // El límite de `Take` es lo que queda por leer de la parte actual
+    file: Take<File>,
+    // Encabezado de cada parte pendiente junto con su rango
+    parts: VecDeque<(Cursor<Vec<u8>>, ByteRange)>,
+    state: PartState,
+}
+
+impl AsyncRead for RangeParts {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = &mut *self;
+
+        loop {
+            let Some((head, range)) = this.parts.front_mut() else {
+                return Poll::Ready(Ok(()));
+            };
+
+            match this.state {
+                PartState::Head => {
+                    if head.position() < head.get_ref().len() as u64 {
+                        return Pin::new(head).poll_read(cx, buf);
+                    }
+
+                    let start = SeekFrom::Start(range.start);
+                    Pin::new(this.file.get_mut()).start_seek(start)?;
+                    this.state = PartState::Seek;
+                }
+                PartState::Seek => {
+                    ready!(Pin::new(this.file.get_mut()).poll_complete(cx))?;
+                    this.file.set_limit(range.length());
+                    this.state = PartState::Body;
+                }
+                PartState::Body => {
+                    if this.file.limit() == 0 {
+                        this.parts.pop_front();
+                        this.state = PartState::Head;
+                        continue;
+                    }
+
+                    let filled = buf.filled().len();
+                    ready!(Pin::new(&mut this.file).poll_read(cx, buf))?;
+
+                    // El archivo se achicó mientras se enviaba
+                    if buf.filled().len() == filled {
+                        return Poll::Ready(Err(
+                            ErrorKind::UnexpectedEof.into()
+                        ));
+                    }
+
+                    return Poll::Ready(Ok(()));
+                }
+            }
+        }
     }
 }
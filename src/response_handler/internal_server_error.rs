@@ -0,0 +1,13 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 500, para errores del servidor al atender la solicitud.
+pub fn internal_server_error() -> HttpResponse {
+    let status_code = StatusCode::InternalServerError;
+    let body = "El servidor no pudo completar la solicitud";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
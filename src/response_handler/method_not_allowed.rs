@@ -0,0 +1,14 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 405, `allow` es la lista de métodos que sí acepta la ruta.
+pub fn method_not_allowed(allow: &str) -> HttpResponse {
+    let status_code = StatusCode::MethodNotAllowed;
+    let body = "El recurso pedido no admite este método";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+    headers.insert("Allow", allow);
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
@@ -1,24 +1,97 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use crate::app_state::AppState;
 use crate::http::HttpRequest;
 use crate::http::HttpResponse;
 
-pub trait Handler<OptionalPath> {
-    fn handle(request: &HttpRequest, path: OptionalPath) -> HttpResponse;
+// Respuesta que produce un `Handler` de forma asíncrona.
+pub type HandlerFuture = Pin<Box<dyn Future<Output = HttpResponse> + Send>>;
+
+/// Atiende una solicitud con acceso al estado compartido del servidor.
+///
+/// Se implementa para cualquier función `async fn(HttpRequest,
+/// Arc<AppState>) -> HttpResponse` o closure equivalente, así los
+/// handlers se registran en el `Router` como valores.
+pub trait Handler: Send + Sync + 'static {
+    fn call(&self, request: HttpRequest, state: Arc<AppState>)
+        -> HandlerFuture;
+}
+
+impl<F, Fut> Handler for F
+where
+    F: Fn(HttpRequest, Arc<AppState>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = HttpResponse> + Send + 'static,
+{
+    fn call(
+        &self,
+        request: HttpRequest,
+        state: Arc<AppState>,
+    ) -> HandlerFuture {
+        Box::pin(self(request, state))
+    }
 }
 
 mod path_not_found;
-pub use path_not_found::PathNotFoundHandler;
+pub use path_not_found::path_not_found;
+
+mod forbidden;
+pub use forbidden::forbidden;
+
+mod error_response;
+pub use error_response::error_response;
+
+mod conflict;
+pub use conflict::conflict;
+
+mod internal_server_error;
+pub use internal_server_error::internal_server_error;
+
+mod method_not_allowed;
+pub use method_not_allowed::method_not_allowed;
+
+mod not_implemented;
+pub use not_implemented::not_implemented;
+
+mod options;
+pub use options::options;
+
+mod moved_permanently;
+pub use moved_permanently::moved_permanently;
+
+mod not_modified;
+pub use not_modified::not_modified;
+
+mod precondition_failed;
+pub use precondition_failed::precondition_failed;
+
+mod range_not_satisfiable;
+pub use range_not_satisfiable::range_not_satisfiable;
+
+mod unsupported_media_type;
+pub use unsupported_media_type::unsupported_media_type;
 
 mod echo;
-pub use echo::EchoHandler;
+pub use echo::echo;
 
 mod path_default;
-pub use path_default::PathDefaultHandler;
+pub use path_default::path_default;
 
 mod user_agent;
-pub use user_agent::UserAgentHandler;
+pub use user_agent::user_agent;
+
+mod store_upload;
+use store_upload::store_upload;
 
 mod get_file;
-pub use get_file::GetFileHandler;
+pub use get_file::get_file;
 
 mod post_file;
-pub use post_file::PostFileHandler;
+pub use post_file::post_file;
+
+mod put_file;
+pub use put_file::put_file;
+
+mod delete_file;
+pub use delete_file::delete_file;
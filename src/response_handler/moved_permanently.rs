@@ -0,0 +1,13 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 301, redirige al cliente a `location`.
+pub fn moved_permanently(location: &str) -> HttpResponse {
+    let status_code = StatusCode::MovedPermanently;
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+    headers.insert("Location", location);
+
+    HttpResponse::new(status_code, Some(headers), Some(location.as_bytes()))
+}
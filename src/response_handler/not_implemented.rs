@@ -0,0 +1,13 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 501, para métodos que ninguna ruta usa.
+pub fn not_implemented() -> HttpResponse {
+    let status_code = StatusCode::NotImplemented;
+    let body = "El servidor no reconoce el método de la solicitud";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
@@ -0,0 +1,15 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+use crate::http::Validators;
+
+/// Respuesta 304, sin body, para un cliente que ya tiene la versión
+/// actual del archivo. Repite sus validadores para que pueda
+/// actualizar su caché.
+pub fn not_modified(validators: &Validators) -> HttpResponse {
+    let status_code = StatusCode::NotModified;
+    let mut headers = HeaderMap::new();
+    validators.insert_into(&mut headers);
+
+    HttpResponse::new(status_code, Some(headers), None)
+}
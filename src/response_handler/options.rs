@@ -0,0 +1,12 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta a `OPTIONS`, `allow` es la lista de métodos de la ruta.
+pub fn options(allow: &str) -> HttpResponse {
+    let status_code = StatusCode::NoContent;
+    let mut headers = HeaderMap::new();
+    headers.insert("Allow", allow);
+
+    HttpResponse::new(status_code, Some(headers), None)
+}
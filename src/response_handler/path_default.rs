@@ -1,18 +1,19 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::app_state::AppState;
+use crate::http::HeaderMap;
 use crate::http::HttpRequest;
 use crate::http::HttpResponse;
-use crate::response_handler::Handler;
+use crate::http::StatusCode;
 
-pub struct PathDefaultHandler;
+pub async fn path_default(
+    _request: HttpRequest,
+    _state: Arc<AppState>,
+) -> HttpResponse {
+    let status_code = StatusCode::Ok;
+    let body = "Todo en orden pero no conozco la ruta";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
 
-impl Handler<()> for PathDefaultHandler {
-    fn handle(_request: &HttpRequest, _path: ()) -> HttpResponse {
-        let status_code = "200";
-        let body = "Todo en orden pero no conozco la ruta";
-        let mut headers = HashMap::new();
-        headers.insert("Content-type".to_string(), "text/plain".to_string());
-
-        HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
-    }
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
 }
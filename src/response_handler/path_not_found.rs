@@ -1,18 +1,13 @@
-use std::collections::HashMap;
-
-use crate::http::HttpRequest;
+use crate::http::HeaderMap;
 use crate::http::HttpResponse;
-use crate::response_handler::Handler;
-
-pub struct PathNotFoundHandler;
+use crate::http::StatusCode;
 
-impl Handler<()> for PathNotFoundHandler {
-    fn handle(_request: &HttpRequest, _path: ()) -> HttpResponse {
-        let status_code = "404";
-        let body = "No existe el recurso que ha sido pedido";
-        let mut headers = HashMap::new();
-        headers.insert("Content-type".to_string(), "text/plain".to_string());
+/// Respuesta 404, para rutas o archivos que no existen.
+pub fn path_not_found() -> HttpResponse {
+    let status_code = StatusCode::NotFound;
+    let body = "No existe el recurso que ha sido pedido";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
 
-        HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
-    }
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
 }
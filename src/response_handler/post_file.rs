@@ -1,30 +1,30 @@
-use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
+use std::sync::Arc;
 
+use crate::app_state::AppState;
+use crate::http::HeaderMap;
 use crate::http::HttpRequest;
 use crate::http::HttpResponse;
-use crate::response_handler::Handler;
+use crate::http::StatusCode;
+use crate::response_handler::store_upload;
 
-pub struct PostFileHandler;
-
-impl Handler<PathBuf> for PostFileHandler {
-    fn handle(request: &HttpRequest, path_dir: PathBuf) -> HttpResponse {
-        let body = String::from_utf8(request.clone().body.unwrap());
-        let file_name = request.uri().data();
-        let path_file = path_dir.join(file_name);
-
-        // crear archivo y escribir contenido
-        fs::write(path_file, body.unwrap()).unwrap();
+/// Guarda el body en un archivo del directorio del servidor.
+///
+/// Con `If-Match` o `If-Unmodified-Since` el archivo solo se reemplaza
+/// si no cambió desde que el cliente lo leyó, y con `If-None-Match: *`
+/// solo se crea si no existe.
+pub async fn post_file(
+    mut request: HttpRequest,
+    state: Arc<AppState>,
+) -> HttpResponse {
+    let file_name = request.raw_param("path").unwrap_or_default().to_string();
+    if let Err(response) = store_upload(&mut request, &state, &file_name).await
+    {
+        return response;
+    }
 
-        let status_code = "201";
-        // let body = "C".as_bytes();
-        let mut headers = HashMap::new();
-        headers.insert(
-            "Content-type".to_string(),
-            "application/octet-stream".to_string(),
-        );
+    let status_code = StatusCode::Created;
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "application/octet-stream");
 
-        HttpResponse::new(status_code, Some(headers), None)
-    }
+    HttpResponse::new(status_code, Some(headers), None)
 }
@@ -0,0 +1,14 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 412, para solicitudes con precondiciones (`If-Match`,
+/// `If-Unmodified-Since`...) que no se cumplen.
+pub fn precondition_failed() -> HttpResponse {
+    let status_code = StatusCode::PreconditionFailed;
+    let body = "El archivo no está en el estado indicado por la solicitud";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
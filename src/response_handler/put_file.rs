@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use crate::app_state::AppState;
+use crate::http::HeaderMap;
+use crate::http::HttpRequest;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+use crate::http::Validators;
+use crate::response_handler::store_upload;
+
+/// Crea o reemplaza un archivo del directorio del servidor con el body.
+///
+/// Responde 201 con `Location` si el archivo no existía y 204 si lo
+/// reemplazó, en ambos casos con los validadores del archivo nuevo.
+/// Repetir la solicitud deja el archivo igual, como pide PUT.
+pub async fn put_file(
+    mut request: HttpRequest,
+    state: Arc<AppState>,
+) -> HttpResponse {
+    let file_name = request.raw_param("path").unwrap_or_default().to_string();
+    let stored = match store_upload(&mut request, &state, &file_name).await {
+        Ok(stored) => stored,
+        Err(response) => return response,
+    };
+
+    let mut headers = HeaderMap::new();
+    if let Ok(metadata) = tokio::fs::metadata(&stored.path).await {
+        Validators::from_metadata(&metadata).insert_into(&mut headers);
+    }
+
+    if !stored.created {
+        return HttpResponse::new(StatusCode::NoContent, Some(headers), None);
+    }
+
+    let location = request.uri().full_path();
+    headers.insert("Location", &location);
+
+    HttpResponse::new(StatusCode::Created, Some(headers), None)
+}
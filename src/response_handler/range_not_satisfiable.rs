@@ -0,0 +1,15 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 416, para rangos que quedan fuera de un recurso de
+/// `length` bytes.
+pub fn range_not_satisfiable(length: u64) -> HttpResponse {
+    let status_code = StatusCode::RangeNotSatisfiable;
+    let body = "Ninguno de los rangos pedidos está dentro del recurso";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+    headers.insert("Content-Range", &format!("bytes */{}", length));
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
@@ -0,0 +1,100 @@
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+use crate::app_state::AppState;
+use crate::errors::Errors;
+use crate::file_root::{is_temp_file, write_atomic, write_new};
+use crate::http::HttpRequest;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+use crate::http::{evaluate_preconditions, Precondition, Validators};
+use crate::response_handler::{
+    conflict, error_response, forbidden, internal_server_error,
+    precondition_failed, unsupported_media_type,
+};
+
+// Archivo que quedó guardado por `store_upload`.
+pub struct StoredUpload {
+    pub path: PathBuf,
+    // El archivo no existía antes de la solicitud
+    pub created: bool,
+}
+
+/// Guarda el body de la solicitud en `file_name`, una ruta sin
+/// decodificar (ver `FileRoot::resolve`), dentro del directorio
+/// del servidor, decodificado según su `Content-Encoding`.
+///
+/// El archivo se reemplaza de forma atómica, quien lo lee mientras
+/// tanto nunca ve un contenido a medias. Con `If-None-Match: *` solo
+/// se crea, nunca se reemplaza un archivo que ya exista. Si no se puede guardar se
+/// devuelve la respuesta de error que corresponde, así cada handler
+/// solo decide el status del caso exitoso.
+pub async fn store_upload(
+    request: &mut HttpRequest,
+    state: &AppState,
+    file_name: &str,
+) -> Result<StoredUpload, HttpResponse> {
+    // Un temporal de otra subida no se puede pisar desde afuera
+    let path = match state.file_root.resolve(file_name) {
+        Ok(path) if !is_temp_file(&path) => path,
+        Err(error @ Errors::InvalidFileName(_)) => {
+            return Err(error_response(StatusCode::BadRequest, &error));
+        }
+        _ => return Err(forbidden()),
+    };
+
+    if let Some(content_type) = request.headers.content_type() {
+        if !state.mime.accepts_upload(&path, content_type) {
+            return Err(unsupported_media_type());
+        }
+    }
+
+    // Un body mal codificado solo afecta a esta solicitud, la conexión
+    // sigue abierta para las siguientes
+    if let Err(error) = request.decode_body() {
+        let status_code = error.status_code().unwrap_or(StatusCode::BadRequest);
+        return Err(error_response(status_code, &error));
+    }
+
+    // Nadie más modifica el archivo entre las precondiciones y la
+    // escritura
+    let _guard = state.path_locks.lock(&path).await;
+
+    let current = match tokio::fs::metadata(&path).await {
+        Ok(metadata) if metadata.is_dir() => return Err(conflict()),
+        Ok(metadata) => Some(Validators::from_metadata(&metadata)),
+        Err(_) => None,
+    };
+    let method = request.method();
+    match evaluate_preconditions(method, &request.headers, current.as_ref()) {
+        Precondition::Passed => {}
+        _ => return Err(precondition_failed()),
+    }
+
+    // El body se guarda tal cual llegó, puede ser binario o estar vacío
+    let body = request.body.as_deref().unwrap_or_default();
+    let create_only = request
+        .headers
+        .get("If-None-Match")
+        .is_some_and(|value| value.trim() == "*");
+    let result = if create_only {
+        write_new(&path, body).await
+    } else {
+        write_atomic(&path, body).await
+    };
+
+    if let Err(error) = result {
+        return Err(match error.kind() {
+            // El directorio que debería contener el archivo no existe
+            ErrorKind::NotFound => conflict(),
+            // Otro proceso lo creó después de las precondiciones
+            ErrorKind::AlreadyExists => precondition_failed(),
+            _ => internal_server_error(),
+        });
+    }
+
+    Ok(StoredUpload {
+        path,
+        created: current.is_none(),
+    })
+}
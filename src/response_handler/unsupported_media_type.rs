@@ -0,0 +1,14 @@
+use crate::http::HeaderMap;
+use crate::http::HttpResponse;
+use crate::http::StatusCode;
+
+/// Respuesta 415, para archivos subidos con un `Content-Type` que no
+/// corresponde a su extensión.
+pub fn unsupported_media_type() -> HttpResponse {
+    let status_code = StatusCode::UnsupportedMediaType;
+    let body = "El tipo de contenido no corresponde al archivo pedido";
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
+
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
+}
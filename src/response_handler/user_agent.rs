@@ -1,23 +1,22 @@
-use std::collections::HashMap;
+use std::sync::Arc;
 
+use crate::app_state::AppState;
+use crate::http::HeaderMap;
 use crate::http::HttpRequest;
 use crate::http::HttpResponse;
-use crate::response_handler::Handler;
+use crate::http::StatusCode;
 
-pub struct UserAgentHandler;
+pub async fn user_agent(
+    request: HttpRequest,
+    _state: Arc<AppState>,
+) -> HttpResponse {
+    // Sin la cabecera 'User-Agent' se responde con el body vacío
+    let user_agent = request.headers.get("User-Agent").unwrap_or("");
 
-impl Handler<()> for UserAgentHandler {
-    fn handle(request: &HttpRequest, _path: ()) -> HttpResponse {
-        let user_agent = request
-            .headers
-            .get("User-Agent")
-            .unwrap_or_else(|| request.headers.get("user-agent").unwrap());
+    let status_code = StatusCode::Ok;
+    let body = user_agent.trim();
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-type", "text/plain");
 
-        let status_code = "200";
-        let body = user_agent.trim();
-        let mut headers = HashMap::new();
-        headers.insert("Content-type".to_string(), "text/plain".to_string());
-
-        HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
-    }
+    HttpResponse::new(status_code, Some(headers), Some(body.as_bytes()))
 }
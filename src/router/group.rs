@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use crate::http::HttpMethod;
+use crate::middleware::Middleware;
+use crate::response_handler::Handler;
+use crate::router::Route;
+
+// ---- -- Route Group -- ---- \\
+
+/// Conjunto de rutas con un prefijo y middleware en común.
+///
+/// Los middleware del grupo solo envuelven a sus rutas, sin importar
+/// si se agregan antes o después de registrarlas.
+pub struct RouteGroup {
+    prefix: String,
+    routes: Vec<Route>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl RouteGroup {
+    pub(super) fn new(prefix: &str) -> Self {
+        RouteGroup {
+            prefix: prefix.trim_end_matches('/').to_string(),
+            routes: Vec::new(),
+            middlewares: Vec::new(),
+        }
+    }
+
+    /// Registra `handler` para `method` en `pattern`, relativo al
+    /// prefijo del grupo.
+    pub fn add<H>(&mut self, method: HttpMethod, pattern: &str, handler: H)
+    where
+        H: Handler,
+    {
+        let pattern = format!("{}{}", self.prefix, pattern);
+        self.routes.push(Route::new(method, &pattern, handler));
+    }
+
+    pub fn get<H>(&mut self, pattern: &str, handler: H)
+    where
+        H: Handler,
+    {
+        self.add(HttpMethod::Get, pattern, handler);
+    }
+
+    pub fn post<H>(&mut self, pattern: &str, handler: H)
+    where
+        H: Handler,
+    {
+        self.add(HttpMethod::Post, pattern, handler);
+    }
+
+    pub fn put<H>(&mut self, pattern: &str, handler: H)
+    where
+        H: Handler,
+    {
+        self.add(HttpMethod::Put, pattern, handler);
+    }
+
+    pub fn delete<H>(&mut self, pattern: &str, handler: H)
+    where
+        H: Handler,
+    {
+        self.add(HttpMethod::Delete, pattern, handler);
+    }
+
+    /// Agrega un middleware que envuelve a las rutas del grupo.
+    #[allow(dead_code)]
+    pub fn middleware<M>(&mut self, middleware: M)
+    where
+        M: Middleware,
+    {
+        self.middlewares.push(Arc::new(middleware));
+    }
+
+    /// Agrega un grupo anidado, sus middleware quedan dentro de los de
+    /// este grupo.
+    #[allow(dead_code)]
+    pub fn group<F>(&mut self, prefix: &str, build: F)
+    where
+        F: FnOnce(&mut RouteGroup),
+    {
+        let mut group = RouteGroup::new(&format!("{}{}", self.prefix, prefix));
+        build(&mut group);
+
+        self.routes.extend(group.into_routes());
+    }
+
+    /// Rutas del grupo con sus middleware delante de los propios.
+    pub(super) fn into_routes(self) -> Vec<Route> {
+        let middlewares = self.middlewares;
+
+        self.routes
+            .into_iter()
+            .map(|mut route| {
+                route.middlewares = middlewares
+                    .iter()
+                    .cloned()
+                    .chain(route.middlewares)
+                    .collect();
+
+                route
+            })
+            .collect()
+    }
+}
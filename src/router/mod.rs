@@ -0,0 +1,575 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::app_state::AppState;
+use crate::http::HttpResponse;
+use crate::http::{HttpMethod, HttpRequest};
+use crate::middleware::{Middleware, Next};
+use crate::response_handler;
+use crate::response_handler::Handler;
+
+mod group;
+pub use group::RouteGroup;
+
+mod pattern;
+pub use pattern::RoutePattern;
+
+// ---- -- Route -- ---- \\
+
+struct Route {
+    method: HttpMethod,
+    pattern: RoutePattern,
+    handler: Box<dyn Handler>,
+    // Middleware de los grupos que contienen la ruta, del más externo
+    // al más interno
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl Route {
+    fn new<H>(method: HttpMethod, pattern: &str, handler: H) -> Self
+    where
+        H: Handler,
+    {
+        Route {
+            method,
+            pattern: RoutePattern::parse(pattern),
+            handler: Box::new(handler),
+            middlewares: Vec::new(),
+        }
+    }
+}
+
+// ---- -- Router -- ---- \\
+
+/// Tabla de rutas del servidor.
+///
+/// Cuando varias rutas coinciden con una solicitud gana la más específica
+/// (ver `RoutePattern::specificity`), a igual especificidad gana la que
+/// se registró primero.
+///
+/// Los middleware globales envuelven a todas las solicitudes, incluso
+/// las que terminan en 404 o 405, y se ejecutan antes que los de los
+/// grupos. En ambos casos el primero registrado es el más externo.
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+    middlewares: Vec<Arc<dyn Middleware>>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Router::default()
+    }
+
+    /// Registra `handler` para las solicitudes `method` cuya ruta
+    /// coincide con `pattern`.
+    pub fn add<H>(&mut self, method: HttpMethod, pattern: &str, handler: H)
+    where
+        H: Handler,
+    {
+        self.routes.push(Route::new(method, pattern, handler));
+    }
+
+    pub fn get<H>(&mut self, pattern: &str, handler: H)
+    where
+        H: Handler,
+    {
+        self.add(HttpMethod::Get, pattern, handler);
+    }
+
+    pub fn post<H>(&mut self, pattern: &str, handler: H)
+    where
+        H: Handler,
+    {
+        self.add(HttpMethod::Post, pattern, handler);
+    }
+
+    /// Agrega un middleware que envuelve a todas las solicitudes.
+    pub fn middleware<M>(&mut self, middleware: M)
+    where
+        M: Middleware,
+    {
+        self.middlewares.push(Arc::new(middleware));
+    }
+
+    /// Registra las rutas que `build` agrega al grupo, todas con el
+    /// prefijo `prefix` y envueltas por los middleware del grupo.
+    pub fn group<F>(&mut self, prefix: &str, build: F)
+    where
+        F: FnOnce(&mut RouteGroup),
+    {
+        let mut group = RouteGroup::new(prefix);
+        build(&mut group);
+
+        self.routes.extend(group.into_routes());
+    }
+
+    /// Decide qué handler atiende la solicitud y devuelve su respuesta,
+    /// el envío queda a cargo de quien administra la conexión.
+    ///
+    /// Los parámetros de la ruta quedan disponibles en `request.params`.
+    /// Si la ruta existe pero no para el método de la solicitud se
+    /// responde 405, y `OPTIONS` se responde automáticamente si no tiene
+    /// un handler registrado. Un método de extensión que ninguna ruta
+    /// usa se responde con 501.
+    ///
+    /// Las solicitudes HEAD sin handler propio usan el de GET, y nunca
+    /// se envía el body de su respuesta.
+    pub async fn route(
+        &self,
+        request: HttpRequest,
+        state: Arc<AppState>,
+    ) -> HttpResponse {
+        let is_head = *request.method() == HttpMethod::Head;
+        let next = Next::router(&self.middlewares, self);
+        let mut response = next.run(request, state).await;
+
+        if is_head {
+            response.skip_body();
+        }
+
+        response
+    }
+
+    /// Final de la cadena de middleware globales.
+    pub(crate) async fn dispatch(
+        &self,
+        mut request: HttpRequest,
+        state: Arc<AppState>,
+    ) -> HttpResponse {
+        let path = request.uri().full_path();
+        let method = request.method().clone();
+
+        let matched = match self.find(&method, &path) {
+            None if method == HttpMethod::Head => {
+                self.find(&HttpMethod::Get, &path)
+            }
+            matched => matched,
+        };
+
+        if let Some((route, params)) = matched {
+            request.params = params;
+
+            let next =
+                Next::handler(&route.middlewares, route.handler.as_ref());
+            return next.run(request, state).await;
+        }
+
+        let is_extension = matches!(method, HttpMethod::Extension(_));
+        if is_extension && !self.routes.iter().any(|r| r.method == method) {
+            return response_handler::not_implemented();
+        }
+
+        let allowed = if request.uri().is_asterisk() {
+            self.allowed_methods(|_| true)
+        } else {
+            self.allowed_methods(|route| route.pattern.matches(&path).is_some())
+        };
+
+        match allowed {
+            None => response_handler::path_not_found(),
+            Some(allow) if method == HttpMethod::Options => {
+                response_handler::options(&allow)
+            }
+            Some(allow) => response_handler::method_not_allowed(&allow),
+        }
+    }
+
+    /// Ruta más específica registrada para `method` que coincide con
+    /// `path`, junto con sus parámetros.
+    fn find(
+        &self,
+        method: &HttpMethod,
+        path: &str,
+    ) -> Option<(&Route, HashMap<String, String>)> {
+        self.routes
+            .iter()
+            .filter(|route| route.method == *method)
+            .filter_map(|route| {
+                let params = route.pattern.matches(path)?;
+                Some((route, params))
+            })
+            .min_by_key(|(route, _)| route.pattern.specificity())
+    }
+
+    /// Valor de la cabecera `Allow` con los métodos de las rutas que
+    /// cumplen `filter`, `None` si ninguna lo cumple.
+    fn allowed_methods<F>(&self, filter: F) -> Option<String>
+    where
+        F: Fn(&Route) -> bool,
+    {
+        let mut methods: Vec<&str> = Vec::new();
+
+        for route in self.routes.iter().filter(|route| filter(route)) {
+            if !methods.contains(&route.method.as_str()) {
+                methods.push(route.method.as_str());
+            }
+        }
+
+        if methods.is_empty() {
+            return None;
+        }
+
+        // Toda ruta GET también responde HEAD
+        let get = methods.iter().position(|m| *m == HttpMethod::Get.as_str());
+        if let Some(position) = get {
+            if !methods.contains(&HttpMethod::Head.as_str()) {
+                methods.insert(position + 1, HttpMethod::Head.as_str());
+            }
+        }
+
+        if !methods.contains(&HttpMethod::Options.as_str()) {
+            methods.push(HttpMethod::Options.as_str());
+        }
+
+        Some(methods.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::Config;
+    use crate::http::{Body, StatusCode};
+    use crate::middleware::MiddlewareFuture;
+
+    /// Handler que responde con el texto `name` y los parámetros de la ruta.
+    fn reply(name: &'static str) -> impl Handler {
+        move |request: HttpRequest, _state| async move {
+            let mut params: Vec<String> = request
+                .params
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect();
+            params.sort();
+
+            let body = format!("{} {}", name, params.join(" "));
+            HttpResponse::new(
+                StatusCode::Ok,
+                None,
+                Some(body.trim().as_bytes()),
+            )
+        }
+    }
+
+    async fn route(router: &Router, request: &str) -> HttpResponse {
+        let request = HttpRequest::try_from(request.as_bytes()).unwrap();
+        let state = AppState::new(Config::default()).unwrap();
+
+        router.route(request, Arc::new(state)).await
+    }
+
+    fn body(response: &HttpResponse) -> &[u8] {
+        match response.body() {
+            Body::Full(bytes) => bytes,
+            _ => b"",
+        }
+    }
+
+    #[tokio::test]
+    async fn test_route_with_params() {
+        let mut router = Router::new();
+        router.get("/users/:id/files/*rest", reply("files"));
+
+        let response =
+            route(&router, "GET /users/7/files/a/b.txt HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(body(&response), b"files id=7 rest=a/b.txt");
+    }
+
+    #[tokio::test]
+    async fn test_route_precedence_does_not_depend_on_order() {
+        let mut router = Router::new();
+        router.get("/files/*path", reply("wildcard"));
+        router.get("/files/:name", reply("param"));
+        router.get("/files/index.html", reply("static"));
+
+        let cases = [
+            ("/files/index.html", "static".as_bytes()),
+            ("/files/a.txt", b"param name=a.txt"),
+            ("/files/dir/a.txt", b"wildcard path=dir/a.txt"),
+        ];
+
+        for (path, expected) in cases {
+            let request = format!("GET {} HTTP/1.1\r\n\r\n", path);
+            let response = route(&router, &request).await;
+
+            assert_eq!(body(&response), expected, "path: {}", path);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_route_same_specificity_first_registered_wins() {
+        let mut router = Router::new();
+        router.get("/a/:x", reply("first"));
+        router.get("/a/:y", reply("second"));
+
+        let response = route(&router, "GET /a/1 HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(body(&response), b"first x=1");
+    }
+
+    #[tokio::test]
+    async fn test_route_not_found() {
+        let mut router = Router::new();
+        router.post("/files/*path", reply("post"));
+
+        let response = route(&router, "GET /missing HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(response.status_code(), StatusCode::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_route_method_not_allowed() {
+        let mut router = Router::new();
+        router.get("/files/*path", reply("get"));
+        router.post("/files/*path", reply("post"));
+        router.get("/echo/*text", reply("echo"));
+
+        let response = route(&router, "POST /echo/x HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(response.status_code(), StatusCode::MethodNotAllowed);
+        assert_eq!(response.headers().get("Allow"), Some("GET, HEAD, OPTIONS"));
+
+        let response = route(&router, "DELETE /files/a HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(response.status_code(), StatusCode::MethodNotAllowed);
+        assert_eq!(
+            response.headers().get("Allow"),
+            Some("GET, HEAD, POST, OPTIONS")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_automatic_options() {
+        let mut router = Router::new();
+        router.get("/", reply("root"));
+        router.get("/files/*path", reply("get"));
+        router.post("/files/*path", reply("post"));
+
+        let response =
+            route(&router, "OPTIONS /files/a HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(response.status_code(), StatusCode::NoContent);
+        assert_eq!(
+            response.headers().get("Allow"),
+            Some("GET, HEAD, POST, OPTIONS")
+        );
+
+        let response = route(&router, "OPTIONS * HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(response.status_code(), StatusCode::NoContent);
+        assert_eq!(
+            response.headers().get("Allow"),
+            Some("GET, HEAD, POST, OPTIONS")
+        );
+
+        let response =
+            route(&router, "OPTIONS /missing HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(response.status_code(), StatusCode::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_route_registered_options_handler() {
+        let mut router = Router::new();
+        router.get("/", reply("root"));
+        router.add(HttpMethod::Options, "/", reply("options"));
+
+        let response = route(&router, "OPTIONS / HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(body(&response), b"options");
+    }
+
+    #[tokio::test]
+    async fn test_route_unknown_method_not_implemented() {
+        let mut router = Router::new();
+        router.get("/files/*path", reply("get"));
+        router.add(HttpMethod::from("PROPFIND"), "/dav/*path", reply("dav"));
+
+        let cases = [
+            ("PROPFIND /files/a", StatusCode::MethodNotAllowed),
+            ("PROPFIND /dav/a", StatusCode::Ok),
+            ("get /files/a", StatusCode::NotImplemented),
+            ("BREW /files/a", StatusCode::NotImplemented),
+            ("PATCH /files/a", StatusCode::MethodNotAllowed),
+        ];
+
+        for (request_line, expected) in cases {
+            let request = format!("{} HTTP/1.1\r\n\r\n", request_line);
+            let response = route(&router, &request).await;
+
+            assert_eq!(response.status_code(), expected, "{}", request_line);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_route_head_uses_get_handler() {
+        let mut router = Router::new();
+        router.get("/echo/*text", reply("echo"));
+
+        let get = route(&router, "GET /echo/abc HTTP/1.1\r\n\r\n").await;
+        let head = route(&router, "HEAD /echo/abc HTTP/1.1\r\n\r\n").await;
+
+        let mut get_output = Vec::new();
+        let mut head_output = Vec::new();
+        get.send_response(&mut get_output).await.unwrap();
+        head.send_response(&mut head_output).await.unwrap();
+
+        let get_output = String::from_utf8(get_output).unwrap();
+        let (get_head, _) = get_output.split_once("\r\n\r\n").unwrap();
+
+        assert!(get_head.contains("Content-Length: 13"));
+        assert_eq!(
+            String::from_utf8(head_output).unwrap(),
+            format!("{}\r\n\r\n", get_head)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_head_never_sends_body() {
+        let router = Router::new();
+
+        let response = route(&router, "HEAD /missing HTTP/1.1\r\n\r\n").await;
+        let mut output = Vec::new();
+        response.send_response(&mut output).await.unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+
+        assert!(output.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(output.ends_with("Content-Length: 39\r\n\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_route_handler_receives_state() {
+        let mut router = Router::new();
+        router.get("/dir", |_request, state: Arc<AppState>| async move {
+            let root = state.file_root.root().display().to_string();
+
+            HttpResponse::new(StatusCode::Ok, None, Some(root.as_bytes()))
+        });
+
+        let response = route(&router, "GET /dir HTTP/1.1\r\n\r\n").await;
+        let root = std::env::current_dir().unwrap().canonicalize().unwrap();
+
+        assert_eq!(body(&response), root.display().to_string().as_bytes());
+    }
+
+    // ---- -- Middleware -- ---- \\
+
+    /// Agrega su nombre a la cabecera `Trace` de la solicitud y a la
+    /// cabecera `Trace-Out` de la respuesta.
+    struct Tag(&'static str);
+
+    impl Middleware for Tag {
+        fn handle<'a>(
+            &'a self,
+            mut request: HttpRequest,
+            state: Arc<AppState>,
+            next: Next<'a>,
+        ) -> MiddlewareFuture<'a> {
+            request.headers.append("Trace", self.0);
+
+            Box::pin(async move {
+                let mut response = next.run(request, state).await;
+                let trace = match response.headers().get("Trace-Out") {
+                    Some(trace) => format!("{}, {}", trace, self.0),
+                    None => self.0.to_string(),
+                };
+                response.add_header("Trace-Out", &trace);
+
+                response
+            })
+        }
+    }
+
+    /// Responde 403 sin llamar al resto de la cadena.
+    struct Deny;
+
+    impl Middleware for Deny {
+        fn handle<'a>(
+            &'a self,
+            _request: HttpRequest,
+            _state: Arc<AppState>,
+            _next: Next<'a>,
+        ) -> MiddlewareFuture<'a> {
+            Box::pin(async { response_handler::forbidden() })
+        }
+    }
+
+    /// Handler que responde con la cabecera `Trace` de la solicitud.
+    async fn trace(
+        request: HttpRequest,
+        _state: Arc<AppState>,
+    ) -> HttpResponse {
+        let trace: Vec<&str> = request.headers.get_all("Trace").collect();
+
+        HttpResponse::new(
+            StatusCode::Ok,
+            None,
+            Some(trace.join(", ").as_bytes()),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_middleware_order() {
+        let mut router = Router::new();
+        router.middleware(Tag("a"));
+        router.group("/files", |files| {
+            files.get("/*path", trace);
+            files.middleware(Tag("c"));
+        });
+        router.middleware(Tag("b"));
+
+        let response = route(&router, "GET /files/x HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(body(&response), b"a, b, c");
+        assert_eq!(response.headers().get("Trace-Out"), Some("c, b, a"));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_group_only_wraps_its_routes() {
+        let mut router = Router::new();
+        router.get("/echo/*text", trace);
+        router.group("/files", |files| {
+            files.middleware(Tag("files"));
+            files.get("/*path", trace);
+        });
+
+        let files = route(&router, "GET /files/x HTTP/1.1\r\n\r\n").await;
+        let echo = route(&router, "GET /echo/x HTTP/1.1\r\n\r\n").await;
+        let missing = route(&router, "GET /missing HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(body(&files), b"files");
+        assert_eq!(body(&echo), b"");
+        assert_eq!(missing.status_code(), StatusCode::NotFound);
+        assert_eq!(missing.headers().get("Trace-Out"), None);
+    }
+
+    #[tokio::test]
+    async fn test_middleware_global_wraps_fallback_responses() {
+        let mut router = Router::new();
+        router.middleware(Tag("global"));
+        router.get("/", trace);
+
+        let response = route(&router, "GET /missing HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(response.status_code(), StatusCode::NotFound);
+        assert_eq!(response.headers().get("Trace-Out"), Some("global"));
+    }
+
+    #[tokio::test]
+    async fn test_middleware_short_circuit() {
+        let mut router = Router::new();
+        router.middleware(Tag("outer"));
+        router.middleware(Deny);
+        router.middleware(Tag("inner"));
+        router.get("/", trace);
+
+        let response = route(&router, "GET / HTTP/1.1\r\n\r\n").await;
+
+        assert_eq!(response.status_code(), StatusCode::Forbidden);
+        assert_eq!(response.headers().get("Trace-Out"), Some("outer"));
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+// ---- -- Route Pattern -- ---- \\
+
+// Segmento de un patrón de ruta.
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    // Texto que debe coincidir exactamente: "echo"
+    Static(String),
+    // Un segmento cualquiera, no vacío: ":id"
+    Param(String),
+    // El resto de la ruta, incluso vacío: "*rest"
+    Wildcard(String),
+}
+
+/// Patrón con el que se registra una ruta, por ejemplo
+/// `/users/:id/files/*rest`.
+///
+/// Los parámetros (`:nombre`) capturan un segmento y el comodín
+/// (`*nombre`), que solo puede ir al final, captura el resto de la ruta.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutePattern {
+    segments: Vec<Segment>,
+}
+
+impl RoutePattern {
+    /// Crea el patrón, entra en pánico si no es válido ya que las rutas
+    /// se registran al iniciar el servidor.
+    pub fn parse(pattern: &str) -> RoutePattern {
+        let pattern = pattern.strip_prefix('/').unwrap_or(pattern);
+        let parts: Vec<&str> =
+            pattern.split('/').filter(|p| !p.is_empty()).collect();
+
+        let segments: Vec<Segment> = parts
+            .iter()
+            .enumerate()
+            .map(|(index, part)| {
+                if let Some(name) = part.strip_prefix(':') {
+                    assert!(
+                        !name.is_empty(),
+                        "Parámetro sin nombre en la ruta"
+                    );
+                    Segment::Param(name.to_string())
+                } else if let Some(name) = part.strip_prefix('*') {
+                    assert!(!name.is_empty(), "Comodín sin nombre en la ruta");
+                    assert!(
+                        index == parts.len() - 1,
+                        "El comodín debe ser el último segmento de la ruta"
+                    );
+                    Segment::Wildcard(name.to_string())
+                } else {
+                    Segment::Static(part.to_string())
+                }
+            })
+            .collect();
+
+        RoutePattern { segments }
+    }
+
+    /// Compara el patrón con una ruta ya normalizada y devuelve los
+    /// parámetros capturados, `None` si no coincide.
+    ///
+    /// Sin comodín la barra final de la ruta se ignora.
+    pub fn matches(&self, path: &str) -> Option<HashMap<String, String>> {
+        let path = path.strip_prefix('/').unwrap_or(path);
+        let mut parts: Vec<&str> = match path {
+            "" => Vec::new(),
+            path => path.split('/').collect(),
+        };
+
+        let has_wildcard =
+            matches!(self.segments.last(), Some(Segment::Wildcard(_)));
+        if !has_wildcard && parts.last() == Some(&"") {
+            parts.pop();
+        }
+
+        let mut params = HashMap::new();
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            match segment {
+                Segment::Static(text) => {
+                    if parts.get(index) != Some(&text.as_str()) {
+                        return None;
+                    }
+                }
+                Segment::Param(name) => match parts.get(index) {
+                    Some(value) if !value.is_empty() => {
+                        params.insert(name.clone(), value.to_string());
+                    }
+                    _ => return None,
+                },
+                Segment::Wildcard(name) => {
+                    let rest = parts.get(index..).unwrap_or_default();
+                    params.insert(name.clone(), rest.join("/"));
+
+                    return Some(params);
+                }
+            }
+        }
+
+        (parts.len() == self.segments.len()).then_some(params)
+    }
+
+    /// Clave para ordenar rutas de la más a la menos específica.
+    ///
+    /// Se compara segmento a segmento: un texto fijo gana a un parámetro
+    /// y un parámetro gana a un comodín.
+    pub fn specificity(&self) -> Vec<u8> {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                Segment::Static(_) => 0,
+                Segment::Param(_) => 1,
+                Segment::Wildcard(_) => 2,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> Option<HashMap<String, String>> {
+        let params = pairs
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+
+        Some(params)
+    }
+
+    #[test]
+    fn test_static_pattern() {
+        let pattern = RoutePattern::parse("/user-agent");
+
+        assert_eq!(pattern.matches("/user-agent"), params(&[]));
+        assert_eq!(pattern.matches("/user-agent/"), params(&[]));
+        assert_eq!(pattern.matches("/user-agent/x"), None);
+        assert_eq!(pattern.matches("/"), None);
+    }
+
+    #[test]
+    fn test_root_pattern() {
+        let pattern = RoutePattern::parse("/");
+
+        assert_eq!(pattern.matches("/"), params(&[]));
+        assert_eq!(pattern.matches("/echo"), None);
+    }
+
+    #[test]
+    fn test_params_and_wildcard() {
+        let pattern = RoutePattern::parse("/users/:id/files/*rest");
+
+        assert_eq!(
+            pattern.matches("/users/42/files/a/b.txt"),
+            params(&[("id", "42"), ("rest", "a/b.txt")])
+        );
+        assert_eq!(
+            pattern.matches("/users/42/files"),
+            params(&[("id", "42"), ("rest", "")])
+        );
+        assert_eq!(
+            pattern.matches("/users/42/files/dir/"),
+            params(&[("id", "42"), ("rest", "dir/")])
+        );
+        assert_eq!(pattern.matches("/users//files/a"), None);
+        assert_eq!(pattern.matches("/users/42"), None);
+    }
+
+    #[test]
+    fn test_specificity() {
+        let static_route = RoutePattern::parse("/files/index.html");
+        let param_route = RoutePattern::parse("/files/:name");
+        let wildcard_route = RoutePattern::parse("/files/*path");
+
+        assert!(static_route.specificity() < param_route.specificity());
+        assert!(param_route.specificity() < wildcard_route.specificity());
+    }
+
+    #[test]
+    #[should_panic(expected = "El comodín debe ser el último segmento")]
+    fn test_wildcard_must_be_last() {
+        RoutePattern::parse("/files/*path/edit");
+    }
+}
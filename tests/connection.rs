@@ -0,0 +1,66 @@
+use tokio::io::AsyncReadExt;
+use tokio::net::TcpStream;
+
+/*
+* Obtiene el tamaño de la primera respuesta completa en 'data' usando la
+* cabecera 'Content-Length' para saber dónde termina el body.
+*/
+fn response_size(data: &[u8]) -> Option<usize> {
+    let text = String::from_utf8_lossy(data);
+    let end_headers = text.find("\r\n\r\n")?;
+
+    let content_length = text[..end_headers]
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(": ")?;
+            key.eq_ignore_ascii_case("content-length")
+                .then(|| value.parse::<usize>().unwrap())
+        })
+        .unwrap();
+
+    let size = end_headers + 4 + content_length;
+    (data.len() >= size).then_some(size)
+}
+
+/*
+* Lee 'count' respuestas HTTP completas desde el stream, en el orden
+* en el que llegan.
+*/
+pub async fn read_responses(
+    stream: &mut TcpStream,
+    count: usize,
+) -> Vec<String> {
+    let mut data = Vec::new();
+    let mut responses = Vec::new();
+    let mut buffer = [0; 1024];
+
+    while responses.len() < count {
+        if let Some(size) = response_size(&data) {
+            let response: Vec<u8> = data.drain(..size).collect();
+            responses.push(String::from_utf8_lossy(&response).to_string());
+            continue;
+        }
+
+        let bytes_read = stream.read(&mut buffer).await.unwrap();
+        assert!(bytes_read > 0, "El servidor cerró la conexión");
+        data.extend_from_slice(&buffer[..bytes_read]);
+    }
+
+    responses
+}
+
+/*
+* Lee una respuesta HTTP completa desde el stream.
+*/
+pub async fn read_response(stream: &mut TcpStream) -> String {
+    read_responses(stream, 1).await.remove(0)
+}
+
+#[path = "connection/keep_alive.rs"]
+mod connection_keep_alive;
+
+#[path = "connection/framing.rs"]
+mod connection_framing;
+
+#[path = "connection/pipelining.rs"]
+mod connection_pipelining;
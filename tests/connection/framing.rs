@@ -0,0 +1,258 @@
+use std::fs;
+use std::io::Read;
+use std::time::Duration;
+
+use flate2::read::GzDecoder;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::read_response;
+
+/*
+* Comprueba que el servidor lee el body completo según 'Content-Length'
+* aunque llegue en varios segmentos TCP y supere los 8 KiB.
+*
+* [test] Responde con el codigo de estatos 201
+* [test] El archivo creado tiene exactamente el body enviado
+*
+* Example:
+* $ curl -v -X POST http://localhost:4221/files/framing_split.txt
+*   --data-binary @archivo_de_20_KiB
+*/
+
+// Ejecuta el servidor como 'just run -- --directory tests/data'
+
+#[tokio::test]
+async fn test_http_framing_body_split_in_segments() {
+    let name_file = "framing_split.txt";
+    let body = "pear raspberry apple ".repeat(1024);
+    let (first, second) = body.split_at(body.len() / 2);
+
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let head = format!(
+        "POST /files/{} HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+        name_file,
+        body.len()
+    );
+    stream.write_all(head.as_bytes()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    stream.write_all(first.as_bytes()).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    stream.write_all(second.as_bytes()).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 201 Created\r\n"));
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let result_path = server_folder.join(name_file);
+
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), body);
+
+    fs::remove_file(result_path).unwrap();
+}
+
+/*
+* Comprueba que el servidor responde 400 y cierra la conexión
+* cuando 'Content-Length' no es un número válido.
+*
+* [test] Responde con el codigo de estatos 400
+* [test] La conexión queda cerrada después de la respuesta
+*/
+
+#[tokio::test]
+async fn test_http_framing_invalid_content_length() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = "POST /files/a HTTP/1.1\r\nContent-Length: abc\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    assert!(response.contains("Connection: close\r\n"));
+
+    let mut buffer = [0; 16];
+    let bytes_read = stream.read(&mut buffer).await.unwrap();
+    assert_eq!(bytes_read, 0);
+}
+
+/*
+* Comprueba que el servidor responde 413 cuando 'Content-Length'
+* supera el tamaño máximo permitido para el body.
+*
+* [test] Responde con el codigo de estatos 413
+*/
+
+#[tokio::test]
+async fn test_http_framing_payload_too_large() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request =
+        "POST /files/a HTTP/1.1\r\nContent-Length: 99999999999\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 413 Content Too Large\r\n"));
+}
+
+/*
+* Comprueba que el servidor decodifica un body enviado con
+* 'Transfer-Encoding: chunked', incluyendo extensiones y trailers.
+*
+* [test] Responde con el codigo de estatos 201
+* [test] El archivo creado tiene el body decodificado
+*
+* Example:
+* $ echo 'pear raspberry' | curl -v -X POST
+*   http://localhost:4221/files/framing_chunked.txt
+*   -H "Transfer-Encoding: chunked" --data-binary @-
+*/
+
+#[tokio::test]
+async fn test_http_framing_chunked_body() {
+    let name_file = "framing_chunked.txt";
+    let chunks = [
+        "5;name=value\r\npear \r\n",
+        "A\r\nraspberry \r\n",
+        "5\r\napple\r\n",
+        "0\r\nChecksum: 1234\r\n\r\n",
+    ];
+
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let head = format!(
+        "POST /files/{} HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n",
+        name_file
+    );
+    stream.write_all(head.as_bytes()).await.unwrap();
+
+    for chunk in chunks {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        stream.write_all(chunk.as_bytes()).await.unwrap();
+    }
+
+    let response = read_response(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 201 Created\r\n"));
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let result_path = server_folder.join(name_file);
+
+    assert_eq!(
+        fs::read_to_string(&result_path).unwrap(),
+        "pear raspberry apple"
+    );
+
+    fs::remove_file(result_path).unwrap();
+}
+
+/*
+* Comprueba que el servidor responde 501 cuando el body usa
+* una 'Transfer-Encoding' que no soporta.
+*
+* [test] Responde con el codigo de estatos 501
+*/
+
+#[tokio::test]
+async fn test_http_framing_unsupported_transfer_encoding() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = "POST /files/a HTTP/1.1\r\nTransfer-Encoding: gzip\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 501 Not Implemented\r\n"));
+}
+
+/*
+* Comprueba que el servidor responde 400 y cierra la conexión cuando
+* recibe datos que no son HTTP, como el inicio de un handshake TLS.
+*
+* [test] Responde con el codigo de estatos 400
+* [test] La conexión queda cerrada después de la respuesta
+*
+* Example:
+* $ curl -v https://localhost:4221/
+*/
+
+#[tokio::test]
+async fn test_http_framing_binary_garbage() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let client_hello = b"\x16\x03\x01\x02\x00\x01\x00\x01\xfc\x03\x03";
+    stream.write_all(client_hello).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    assert!(response.contains("Connection: close\r\n"));
+
+    let mut buffer = [0; 16];
+    let bytes_read = stream.read(&mut buffer).await.unwrap();
+    assert_eq!(bytes_read, 0);
+}
+
+/*
+* Comprueba que el servidor responde 414 cuando la URI de la
+* línea de solicitud es demasiado larga.
+*
+* [test] Responde con el codigo de estatos 414
+*/
+
+#[tokio::test]
+async fn test_http_framing_uri_too_long() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = format!("GET /echo/{} HTTP/1.1\r\n\r\n", "a".repeat(10000));
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 414 URI Too Long\r\n"));
+}
+
+/*
+* Comprueba que el servidor no envía 'Transfer-Encoding: chunked' a un
+* cliente HTTP/1.0, que no lo entiende.
+*
+* [test] Responde con la versión HTTP/1.0
+* [test] Responde el archivo comprimido sin 'Transfer-Encoding' ni
+*        'Content-Length', cerrando la conexión al terminar el body
+*
+* Example:
+* $ curl -v --http1.0 -H "Accept-Encoding: gzip" \
+*   http://localhost:4221/files/index.html
+*/
+
+#[tokio::test]
+async fn test_http_framing_http_1_0_compressed_file() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request =
+        "GET /files/index.html HTTP/1.0\r\nAccept-Encoding: gzip\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    // El servidor cierra la conexión después del body
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response).await.unwrap();
+
+    let end_headers =
+        response.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+    let head = String::from_utf8_lossy(&response[..end_headers]);
+    let body = &response[end_headers + 4..];
+
+    assert!(head.starts_with("HTTP/1.0 200 OK\r\n"));
+    assert!(head.contains("Content-Encoding: gzip"));
+    assert!(head.contains("Connection: close"));
+    assert!(!head.contains("Transfer-Encoding"));
+    assert!(!head.contains("Content-Length"));
+
+    let mut decoded = Vec::new();
+    GzDecoder::new(body).read_to_end(&mut decoded).unwrap();
+
+    let this_file = std::path::Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    assert_eq!(decoded, fs::read(server_folder.join("index.html")).unwrap());
+}
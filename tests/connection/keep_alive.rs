@@ -0,0 +1,143 @@
+use std::time::{Duration, Instant};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use crate::{read_response, read_responses};
+
+/*
+* Comprueba que el servidor atiende varias solicitudes
+* sobre la misma conexión TCP.
+*
+* [test] Responde ambas solicitudes con el codigo de estatos 200
+* [test] Responde con el header {'Connection': 'keep-alive'}
+*
+* Example:
+* $ curl -v http://localhost:4221/echo/abc http://localhost:4221/echo/def
+*/
+
+#[tokio::test]
+async fn test_http_connection_keep_alive_reuses_connection() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    for data in ["abc", "def"] {
+        let request = format!("GET /echo/{} HTTP/1.1\r\n\r\n", data);
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let response = read_response(&mut stream).await;
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains("Connection: keep-alive\r\n"));
+        assert!(response.ends_with(data));
+    }
+}
+
+/*
+* Comprueba que el servidor cierra la conexión cuando el cliente
+* envía el header 'Connection: close'.
+*
+* [test] Responde con el header {'Connection': 'close'}
+* [test] La conexión queda cerrada después de la respuesta
+*
+* Example:
+* $ curl -v http://localhost:4221/ -H "Connection: close"
+*/
+
+#[tokio::test]
+async fn test_http_connection_close() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = "GET /echo/abc HTTP/1.1\r\nConnection: close\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.contains("Connection: close\r\n"));
+
+    let mut buffer = [0; 16];
+    let bytes_read = stream.read(&mut buffer).await.unwrap();
+    assert_eq!(bytes_read, 0);
+}
+
+/*
+* Comprueba que una solicitud HTTP/1.0 sin el header
+* 'Connection: keep-alive' no es persistente.
+*
+* Example:
+* $ curl -v --http1.0 http://localhost:4221/
+*/
+
+#[tokio::test]
+async fn test_http_connection_http_1_0_closes_by_default() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = "GET /echo/abc HTTP/1.0\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.contains("Connection: close\r\n"));
+
+    let mut buffer = [0; 16];
+    let bytes_read = stream.read(&mut buffer).await.unwrap();
+    assert_eq!(bytes_read, 0);
+}
+
+/*
+* Comprueba que el servidor cierra una conexión que queda inactiva
+* más de 5 segundos después de la última respuesta.
+*
+* [test] La conexión sigue abierta mientras no pasan los 5 segundos
+* [test] La conexión queda cerrada después de los 5 segundos
+*/
+
+#[tokio::test]
+async fn test_http_connection_idle_timeout() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = "GET /echo/abc HTTP/1.1\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let response = read_response(&mut stream).await;
+    assert!(response.contains("Connection: keep-alive\r\n"));
+
+    let start = Instant::now();
+    let mut buffer = [0; 16];
+    let bytes_read = timeout(Duration::from_secs(8), stream.read(&mut buffer))
+        .await
+        .expect("El servidor no cerró la conexión inactiva")
+        .unwrap();
+
+    assert_eq!(bytes_read, 0);
+    assert!(start.elapsed() >= Duration::from_secs(4));
+}
+
+/*
+* Comprueba que el servidor atiende como máximo 100 solicitudes por
+* conexión.
+*
+* [test] Las primeras 99 respuestas tienen el header {'Connection': 'keep-alive'}
+* [test] La respuesta 100 tiene el header {'Connection': 'close'}
+* [test] La conexión queda cerrada después de la respuesta 100
+*/
+
+#[tokio::test]
+async fn test_http_connection_max_requests() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = "GET /echo/abc HTTP/1.1\r\n\r\n";
+    stream
+        .write_all(request.repeat(100).as_bytes())
+        .await
+        .unwrap();
+
+    let responses = read_responses(&mut stream, 100).await;
+
+    for response in &responses[..99] {
+        assert!(response.contains("Connection: keep-alive\r\n"));
+    }
+    assert!(responses[99].contains("Connection: close\r\n"));
+
+    let mut buffer = [0; 16];
+    let bytes_read = stream.read(&mut buffer).await.unwrap();
+    assert_eq!(bytes_read, 0);
+}
@@ -0,0 +1,116 @@
+use std::fs;
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+
+use crate::read_responses;
+
+/*
+* Comprueba que el servidor responde, en orden, varias solicitudes
+* enviadas en una sola escritura sin esperar las respuestas.
+*
+* [test] Responde las tres solicitudes en el orden en que fueron enviadas
+* [test] Cada respuesta corresponde a su solicitud
+*/
+
+#[tokio::test]
+async fn test_http_pipelining_three_get_requests() {
+    let requests = [
+        "GET /echo/first HTTP/1.1\r\n\r\n",
+        "GET /user-agent HTTP/1.1\r\nUser-Agent: pipelined/1.0\r\n\r\n",
+        "GET /echo/third HTTP/1.1\r\n\r\n",
+    ];
+
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+    stream
+        .write_all(requests.concat().as_bytes())
+        .await
+        .unwrap();
+
+    let responses = read_responses(&mut stream, 3).await;
+
+    assert!(responses[0].ends_with("\r\n\r\nfirst"));
+    assert!(responses[1].ends_with("\r\n\r\npipelined/1.0"));
+    assert!(responses[2].ends_with("\r\n\r\nthird"));
+}
+
+/*
+* Comprueba que el body de una solicitud en pipeline no absorbe
+* las solicitudes que la siguen.
+*
+* [test] Responde con el codigo de estatos 201 al POST
+* [test] El archivo creado contiene solo el body del POST
+* [test] Las solicitudes siguientes son respondidas
+*/
+
+// Ejecuta el servidor como 'just run -- --directory tests/data'
+
+#[tokio::test]
+async fn test_http_pipelining_post_with_body() {
+    let name_file = "pipelining_post.txt";
+    let requests = [
+        format!(
+            "POST /files/{} HTTP/1.1\r\nContent-Length: 5\r\n\r\nmango",
+            name_file
+        ),
+        "GET /echo/second HTTP/1.1\r\n\r\n".to_string(),
+        "GET /echo/third HTTP/1.1\r\nConnection: close\r\n\r\n".to_string(),
+    ];
+
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+    stream
+        .write_all(requests.concat().as_bytes())
+        .await
+        .unwrap();
+
+    let responses = read_responses(&mut stream, 3).await;
+
+    assert!(responses[0].starts_with("HTTP/1.1 201 Created\r\n"));
+    assert!(responses[1].ends_with("\r\n\r\nsecond"));
+    assert!(responses[2].ends_with("\r\n\r\nthird"));
+    assert!(responses[2].contains("Connection: close\r\n"));
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let result_path = server_folder.join(name_file);
+
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), "mango");
+
+    fs::remove_file(result_path).unwrap();
+}
+
+/*
+* Comprueba que un body con un Content-Encoding no soportado o mal
+* codificado no cierra la conexión.
+*
+* [test] Responde con el codigo de estatos 415 al body en 'br'
+* [test] Responde con el codigo de estatos 400 al gzip inválido
+* [test] Las solicitudes siguientes son respondidas
+*/
+
+#[tokio::test]
+async fn test_http_pipelining_after_content_encoding_error() {
+    let requests = [
+        "POST /files/pipelining_br.txt HTTP/1.1\r\n\
+         Content-Encoding: br\r\nContent-Length: 5\r\n\r\nmango",
+        "POST /files/pipelining_gzip.txt HTTP/1.1\r\n\
+         Content-Encoding: gzip\r\nContent-Length: 5\r\n\r\nmango",
+        "GET /echo/third HTTP/1.1\r\nConnection: close\r\n\r\n",
+    ];
+
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+    stream
+        .write_all(requests.concat().as_bytes())
+        .await
+        .unwrap();
+
+    let responses = read_responses(&mut stream, 3).await;
+
+    assert!(responses[0].starts_with("HTTP/1.1 415 Unsupported Media Type"));
+    assert!(responses[0].contains("Accept-Encoding: gzip, deflate\r\n"));
+    assert!(responses[0].contains("Connection: keep-alive\r\n"));
+    assert!(responses[1].starts_with("HTTP/1.1 400 Bad Request\r\n"));
+    assert!(responses[1].contains("Connection: keep-alive\r\n"));
+    assert!(responses[2].ends_with("\r\n\r\nthird"));
+}
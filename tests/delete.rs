@@ -0,0 +1,2 @@
+#[path = "delete/command_files.rs"]
+mod delete_command_files;
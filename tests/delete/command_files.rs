@@ -0,0 +1,78 @@
+use std::fs;
+use std::path::Path;
+
+use reqwest::Client;
+
+/*
+* Comprueba que el comando 'files' borra archivos con DELETE.
+*
+* Caso 1 (Archivo existente y no existente):
+*
+* $ curl -v -X DELETE http://localhost:4221/files/mango.txt
+*
+* [test] Responde con el codigo de estatos 204 y borra el archivo
+* [test] Responde sin el header 'Content-Type'
+* [test] Responde con el codigo de estatos 404 si el archivo no existe
+*/
+
+// Ejecuta el servidor como 'just run -- --directory tests/data'
+
+#[tokio::test]
+async fn test_http_delete_command_files() {
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+
+    let name_file = format!("delete_mango_{}.txt", std::process::id());
+    let url = format!("http://localhost:4221/files/{}", name_file);
+    let result_path = server_folder.join(&name_file);
+
+    fs::write(&result_path, "mango").unwrap();
+
+    let http_client = Client::new();
+    let response = http_client.delete(&url).send().await.unwrap();
+
+    assert_eq!(response.status(), 204);
+    assert!(!response.headers().contains_key("content-type"));
+    assert!(!result_path.exists());
+
+    let response = http_client.delete(&url).send().await.unwrap();
+
+    assert_eq!(response.status(), 404);
+}
+
+/*
+* Comprueba que el comando 'files' no borra un archivo que cambió desde
+* que el cliente lo leyó.
+*
+* Caso 2 (Precondiciones):
+*
+* $ curl -v -X DELETE http://localhost:4221/files/mango.txt \
+*   -H 'If-Match: "otro"'
+*
+* [test] Responde con el codigo de estatos 412 y conserva el archivo
+*/
+
+#[tokio::test]
+async fn test_http_delete_command_files_precondition_failed() {
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+
+    let name_file = format!("delete_if_match_{}.txt", std::process::id());
+    let url = format!("http://localhost:4221/files/{}", name_file);
+    let result_path = server_folder.join(&name_file);
+
+    fs::write(&result_path, "mango").unwrap();
+
+    let http_client = Client::new();
+    let response = http_client
+        .delete(&url)
+        .header("If-Match", "\"otro\"")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 412);
+    assert!(result_path.exists());
+
+    fs::remove_file(result_path).unwrap();
+}
@@ -12,3 +12,12 @@ mod get_command_user_agent;
 
 #[path = "get/command_files.rs"]
 mod get_command_files;
+
+#[path = "get/command_files_range.rs"]
+mod get_command_files_range;
+
+#[path = "get/command_files_conditional.rs"]
+mod get_command_files_conditional;
+
+#[path = "get/command_files_directory.rs"]
+mod get_command_files_directory;
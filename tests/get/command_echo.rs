@@ -147,3 +147,52 @@ async fn test_http_get_command_echo_with_accept_encoding_invalid() {
         assert_eq!(body, *data);
     }
 }
+
+/*
+* Comprueba que el query string no forma parte del texto
+* que devuelve el comando 'echo'.
+*
+* [test] Responde con el codiigo de estatos 200
+* [test] Responde con el body sin el query string
+*
+* Example:
+* $ curl -v "http://localhost:4221/echo/strawberry?color=red&size=2"
+*/
+
+#[tokio::test]
+async fn test_http_get_command_echo_ignores_query_string() {
+    let url = "http://localhost:4221/echo/strawberry?color=red&size=2";
+
+    let http_client = Client::new();
+    let response = http_client.get(url).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-length"], "10");
+
+    let body = response.text().await.unwrap();
+    assert_eq!(body, "strawberry");
+}
+
+/*
+* Comprueba que el comando 'echo' decodifica el texto, incluso una
+* "/" codificada.
+*
+* [test] Responde con el codigo de estatos 200
+* [test] Responde con el texto decodificado como body
+*
+* Example:
+* $ curl -v "http://localhost:4221/echo/a%2Fb%20c"
+*/
+
+#[tokio::test]
+async fn test_http_get_command_echo_decodes_text() {
+    let url = "http://localhost:4221/echo/a%2Fb%20c";
+
+    let http_client = Client::new();
+    let response = http_client.get(url).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+
+    let body = response.text().await.unwrap();
+    assert_eq!(body, "a/b c");
+}
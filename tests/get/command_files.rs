@@ -1,7 +1,10 @@
 use std::fs;
-use std::io::Write;
+use std::io::{Read, Write};
 
+use flate2::read::GzDecoder;
 use reqwest::Client;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
 
 /*
 * Comprueba que el servidor responde al comando 'files'
@@ -12,7 +15,7 @@ use reqwest::Client;
 * $ curl -v http://localhost:4221/files/raspberry_blueberry_mango_raspberry
 *
 * [test] Responde con el codigo de estatos 200
-* [test] Responde con el header {'Content-Type': 'text/plain'}
+* [test] Responde con el header {'Content-Type': '<tipo del archivo>'}
 * [test] Responde con el header {'Content-Length': '<length>'}
 * [test] Responde con el valor binario del archivo como body
 */
@@ -21,13 +24,16 @@ use reqwest::Client;
 
 #[tokio::test]
 async fn test_http_get_command_files_exist_file() {
-    let name_files = ["index.html", "Rick_and_Morty.jpg"];
+    let name_files = [
+        ("index.html", "text/html; charset=utf-8"),
+        ("Rick_and_Morty.jpg", "image/jpeg"),
+    ];
     let host = String::from("http://localhost:4221");
 
     // Crea un directorio temporal
     let temp_dir = std::env::temp_dir();
 
-    for name_file in &name_files {
+    for (name_file, content_type) in &name_files {
         let path = format!("files/{}", name_file);
         let url = format!("{}/{}", host, path);
 
@@ -36,10 +42,7 @@ async fn test_http_get_command_files_exist_file() {
 
         assert_eq!(response.status(), 200);
 
-        assert_eq!(
-            response.headers()["content-type"],
-            "application/octet-stream"
-        );
+        assert_eq!(response.headers()["content-type"], *content_type);
 
         let content_length = response.headers()["content-length"].clone();
         let temp_file_path = temp_dir.join(name_file);
@@ -89,3 +92,240 @@ async fn test_http_get_command_files_no_exist_file() {
         assert_eq!(body, "No existe el recurso que ha sido pedido");
     }
 }
+
+/*
+* Comprueba que el servidor decodifica y normaliza la ruta antes
+* de buscar el archivo.
+*
+* Caso 3 (Ruta codificada):
+*
+* $ curl -v --path-as-is "http://localhost:4221/files//./my%20report.txt"
+*
+* [test] Responde con el codigo de estatos 200
+* [test] Responde con el contenido del archivo 'my report.txt'
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_encoded_path() {
+    let name_file = "my report.txt";
+    let host = String::from("http://localhost:4221");
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let file_path = server_folder.join(name_file);
+    fs::write(&file_path, "pear raspberry apple").unwrap();
+
+    let paths = ["files/my%20report.txt", "files//./my%20report.txt"];
+
+    for path in &paths {
+        let url = format!("{}/{}", host, path);
+
+        let http_client = Client::new();
+        let response = http_client.get(&url).send().await.unwrap();
+
+        assert_eq!(response.status(), 200, "path: {}", path);
+
+        let body = response.text().await.unwrap();
+        assert_eq!(body, "pear raspberry apple");
+    }
+
+    fs::remove_file(file_path).unwrap();
+}
+
+/*
+* Comprueba que el servidor rechaza una ruta con un NUL codificado.
+*
+* Caso 4 (Ruta inválida):
+*
+* $ curl -v "http://localhost:4221/files/index.html%00.jpg"
+*
+* [test] Responde con el codigo de estatos 400
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_encoded_nul() {
+    let url = "http://localhost:4221/files/index.html%00.jpg";
+
+    let http_client = Client::new();
+    let response = http_client.get(url).send().await.unwrap();
+
+    assert_eq!(response.status(), 400);
+}
+
+/*
+* Comprueba que el comando 'files' no permite leer archivos fuera
+* del directorio del servidor.
+*
+* Caso 5 (Directory traversal):
+*
+* $ curl -v --path-as-is http://localhost:4221/files/../../etc/passwd
+*
+* [test] Las rutas con ".." (literales o codificados) no salen del directorio
+* [test] Un enlace simbólico que apunta fuera del directorio responde 403
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_directory_traversal() {
+    let paths = [
+        "/files/../../etc/passwd",
+        "/files/%2e%2e/%2e%2e/etc/passwd",
+        "/files/..%2f..%2fetc%2fpasswd",
+        "/files//etc/passwd",
+    ];
+
+    for path in &paths {
+        let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+        let request =
+            format!("GET {} HTTP/1.1\r\nConnection: close\r\n\r\n", path);
+        stream.write_all(request.as_bytes()).await.unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(
+            response.starts_with("HTTP/1.1 404 Not Found\r\n"),
+            "{}",
+            path
+        );
+        assert!(!response.contains("root:"));
+    }
+}
+
+#[tokio::test]
+async fn test_http_get_command_files_symlink_outside_directory() {
+    let name_link = "link_to_outside";
+    let host = String::from("http://localhost:4221");
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let link_path = server_folder.join(name_link);
+
+    let _ = fs::remove_file(&link_path);
+    std::os::unix::fs::symlink("/etc", &link_path).unwrap();
+
+    let url = format!("{}/files/{}/passwd", host, name_link);
+    let http_client = Client::new();
+    let response = http_client.get(&url).send().await.unwrap();
+
+    fs::remove_file(link_path).unwrap();
+
+    assert_eq!(response.status(), 403);
+}
+
+/*
+* Comprueba que los archivos se envían comprimidos cuando el
+* cliente lo acepta.
+*
+* [test] Responde con el codigo de estatos 200
+* [test] Responde con los headers {'Content-Encoding': 'gzip'} y
+*        {'Vary': 'Accept-Encoding'}
+* [test] Responde en chunks, sin 'Content-Length'
+* [test] Responde con el archivo comprimido como body
+*
+* Example:
+* $ curl -v --compressed http://localhost:4221/files/index.html
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_compressed() {
+    let url = "http://localhost:4221/files/index.html";
+
+    let http_client = Client::new();
+    let response = http_client
+        .get(url)
+        .header("Accept-Encoding", "deflate;q=0.5, gzip")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+    assert_eq!(response.headers()["vary"], "Accept-Encoding");
+    assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    assert!(!response.headers().contains_key("content-length"));
+
+    let body_bytes = response.bytes().await.unwrap();
+    let mut body = Vec::new();
+    GzDecoder::new(&body_bytes[..])
+        .read_to_end(&mut body)
+        .unwrap();
+
+    assert_eq!(body, fs::read("tests/data/index.html").unwrap());
+}
+
+/*
+* Comprueba que el tipo de un archivo sin extensión se adivina a
+* partir de su contenido.
+*
+* $ curl -v http://localhost:4221/files/page_without_extension
+*
+* [test] Responde con el codigo de estatos 200
+* [test] Responde con el header {'Content-Type': 'text/html; charset=utf-8'}
+* [test] Responde con el archivo completo como body
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_sniffed_content_type() {
+    let name_file = format!("sniffed_{}", std::process::id());
+    let content = format!("<!DOCTYPE html>\n{}", "<p>Rick</p>\n".repeat(100));
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let file_path = server_folder.join(&name_file);
+    fs::write(&file_path, &content).unwrap();
+
+    let url = format!("http://localhost:4221/files/{}", name_file);
+
+    let http_client = Client::new();
+    let response = http_client.get(&url).send().await.unwrap();
+
+    fs::remove_file(file_path).unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/html; charset=utf-8"
+    );
+    assert_eq!(response.text().await.unwrap(), content);
+}
+
+/*
+* Comprueba que el archivo temporal de una subida en curso no se puede
+* leer ni borrar.
+*
+* $ curl -v http://localhost:4221/files/.mango.txt.<pid>-0.tmp
+* $ curl -v -X DELETE http://localhost:4221/files/.mango.txt.<pid>-0.tmp
+*
+* [test] Responde con el codigo de estatos 404 al GET y al DELETE
+* [test] Responde con el codigo de estatos 403 al PUT
+* [test] El archivo temporal no se modifica
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_upload_temp_file() {
+    let name_file = format!(".mango.txt.{}-0.tmp", std::process::id());
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let file_path = server_folder.join(&name_file);
+    fs::write(&file_path, "man").unwrap();
+
+    let url = format!("http://localhost:4221/files/{}", name_file);
+
+    let http_client = Client::new();
+    let get = http_client.get(&url).send().await.unwrap();
+    let delete = http_client.delete(&url).send().await.unwrap();
+    let put = http_client.put(&url).body("kiwi").send().await.unwrap();
+
+    let content = fs::read_to_string(&file_path).unwrap();
+    fs::remove_file(file_path).unwrap();
+
+    assert_eq!(get.status(), 404);
+    assert_eq!(delete.status(), 404);
+    assert_eq!(put.status(), 403);
+    assert_eq!(content, "man");
+}
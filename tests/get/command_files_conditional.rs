@@ -0,0 +1,116 @@
+use reqwest::Client;
+
+const URL: &str = "http://localhost:4221/files/index.html";
+
+/*
+* Comprueba que el servidor responde las solicitudes condicionales del
+* comando 'files'.
+*
+* Caso 1 (If-None-Match e If-Modified-Since):
+*
+* $ curl -v -H 'If-None-Match: "<etag>"' http://localhost:4221/files/index.html
+* $ curl -v -H "If-Modified-Since: <fecha>" http://localhost:4221/files/index.html
+*
+* [test] Responde con los headers 'ETag' y 'Last-Modified'
+* [test] Responde con el codigo de estatos 304 y sin body si el archivo
+*        no cambió
+* [test] Responde con el codigo de estatos 200 si el archivo cambió
+* [test] If-None-Match tiene prioridad sobre If-Modified-Since
+*/
+
+// Ejecuta el servidor como 'just run -- --directory tests/data'
+
+#[tokio::test]
+async fn test_http_get_command_files_not_modified() {
+    let http_client = Client::new();
+    let response = http_client.get(URL).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+    let last_modified = response.headers()["last-modified"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    let weak_etag = format!("W/{}", etag.trim_start_matches("W/"));
+
+    let cases = [
+        (vec![("If-None-Match", etag.as_str())], 304),
+        (vec![("If-None-Match", weak_etag.as_str())], 304),
+        (vec![("If-None-Match", "\"otro\", *")], 304),
+        (vec![("If-None-Match", "\"otro\"")], 200),
+        (vec![("If-Modified-Since", last_modified.as_str())], 304),
+        (
+            vec![("If-Modified-Since", "Thu, 01 Jan 1970 00:00:00 GMT")],
+            200,
+        ),
+        (
+            vec![
+                ("If-None-Match", "\"otro\""),
+                ("If-Modified-Since", last_modified.as_str()),
+            ],
+            200,
+        ),
+    ];
+
+    for (headers, status) in cases {
+        let mut request = http_client.get(URL);
+        for (name, value) in &headers {
+            request = request.header(*name, *value);
+        }
+
+        let response = request.send().await.unwrap();
+
+        assert_eq!(response.status(), status, "{:?}", headers);
+        assert_eq!(response.headers()["etag"], etag.as_str());
+
+        let body = response.bytes().await.unwrap();
+        assert_eq!(body.is_empty(), status == 304, "{:?}", headers);
+    }
+}
+
+/*
+* Comprueba que el servidor responde las solicitudes condicionales del
+* comando 'files'.
+*
+* Caso 2 (If-Match e If-Unmodified-Since):
+*
+* $ curl -v -H 'If-Match: "otro"' http://localhost:4221/files/index.html
+*
+* [test] Responde con el codigo de estatos 412 si el archivo cambió
+* [test] Responde con el codigo de estatos 200 si no cambió
+* [test] If-Match tiene prioridad sobre If-Unmodified-Since
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_precondition_failed() {
+    let old_date = "Thu, 01 Jan 1970 00:00:00 GMT";
+
+    let http_client = Client::new();
+    let response = http_client.get(URL).send().await.unwrap();
+    let last_modified = response.headers()["last-modified"]
+        .to_str()
+        .unwrap()
+        .to_string();
+
+    let cases = [
+        (vec![("If-Match", "\"otro\"")], 412),
+        (vec![("If-Match", "*")], 200),
+        (vec![("If-Unmodified-Since", old_date)], 412),
+        (vec![("If-Unmodified-Since", last_modified.as_str())], 200),
+        (
+            vec![("If-Match", "*"), ("If-Unmodified-Since", old_date)],
+            200,
+        ),
+    ];
+
+    for (headers, status) in cases {
+        let mut request = http_client.get(URL);
+        for (name, value) in &headers {
+            request = request.header(*name, *value);
+        }
+
+        let response = request.send().await.unwrap();
+
+        assert_eq!(response.status(), status, "{:?}", headers);
+    }
+}
@@ -0,0 +1,110 @@
+use std::fs;
+use std::path::Path;
+
+use reqwest::redirect::Policy;
+use reqwest::Client;
+
+/*
+* Comprueba que el comando 'files' sirve el index.html de un directorio.
+*
+* Caso 1 (Directorio con index.html):
+*
+* $ curl -v http://localhost:4221/files/
+* $ curl -v http://localhost:4221/files/<directorio>
+*
+* [test] Responde con el codigo de estatos 200 y el index.html como body
+* [test] Responde con el codigo de estatos 301 si falta la "/" final
+* [test] Responde con el header {'Location': '<ruta>/'}
+*/
+
+// Ejecuta el servidor como 'just run -- --directory tests/data'
+
+#[tokio::test]
+async fn test_http_get_command_files_directory_index() {
+    let host = "http://localhost:4221";
+    let name_dir = format!("index_dir_{}", std::process::id());
+
+    let server_folder = Path::new(file!()).ancestors().nth(2).unwrap();
+    let server_folder = server_folder.join("data");
+    let dir_path = server_folder.join(&name_dir);
+
+    fs::create_dir_all(&dir_path).unwrap();
+    fs::write(dir_path.join("index.html"), "<h1>Sub</h1>").unwrap();
+
+    let http_client =
+        Client::builder().redirect(Policy::none()).build().unwrap();
+
+    let cases = [
+        ("/files/".to_string(), server_folder.join("index.html")),
+        (format!("/files/{}/", name_dir), dir_path.join("index.html")),
+    ];
+
+    for (path, index) in &cases {
+        let url = format!("{}{}", host, path);
+        let response = http_client.get(&url).send().await.unwrap();
+
+        assert_eq!(response.status(), 200, "{}", path);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/html; charset=utf-8"
+        );
+
+        let body = response.bytes().await.unwrap();
+        assert_eq!(&body[..], &fs::read(index).unwrap()[..], "{}", path);
+    }
+
+    let redirects = [
+        ("/files".to_string(), "/files/".to_string()),
+        (
+            format!("/files/{}", name_dir),
+            format!("/files/{}/", name_dir),
+        ),
+        (
+            format!("/files/{}?a=1", name_dir),
+            format!("/files/{}/?a=1", name_dir),
+        ),
+    ];
+
+    for (path, location) in &redirects {
+        let url = format!("{}{}", host, path);
+        let response = http_client.get(&url).send().await.unwrap();
+
+        assert_eq!(response.status(), 301, "{}", path);
+        assert_eq!(response.headers()["location"], location.as_str());
+    }
+
+    fs::remove_dir_all(dir_path).unwrap();
+}
+
+/*
+* Comprueba que el comando 'files' no lista los directorios sin
+* index.html cuando el servidor no usa '--autoindex'.
+*
+* Caso 2 (Directorio sin index.html):
+*
+* $ curl -v http://localhost:4221/files/<directorio>/
+*
+* [test] Responde con el codigo de estatos 404
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_directory_without_index() {
+    let name_dir = format!("empty_dir_{}", std::process::id());
+
+    let server_folder = Path::new(file!()).ancestors().nth(2).unwrap();
+    let dir_path = server_folder.join("data").join(&name_dir);
+    fs::create_dir_all(&dir_path).unwrap();
+    fs::write(dir_path.join("a.txt"), "a").unwrap();
+
+    let http_client =
+        Client::builder().redirect(Policy::none()).build().unwrap();
+
+    for path in [format!("{}/", name_dir), name_dir.clone()] {
+        let url = format!("http://localhost:4221/files/{}", path);
+        let response = http_client.get(&url).send().await.unwrap();
+
+        assert_eq!(response.status(), 404, "{}", path);
+    }
+
+    fs::remove_dir_all(dir_path).unwrap();
+}
@@ -0,0 +1,231 @@
+use std::fs;
+
+use reqwest::Client;
+
+const INDEX: &str = "tests/data/index.html";
+
+/*
+* Comprueba que el servidor responde partes de un archivo con la
+* cabecera 'Range'.
+*
+* Caso 1 (Un rango y un rango de sufijo):
+*
+* $ curl -v -H "Range: bytes=0-9" http://localhost:4221/files/index.html
+* $ curl -v -H "Range: bytes=-10" http://localhost:4221/files/index.html
+*
+* [test] Responde con el codigo de estatos 206
+* [test] Responde con el header {'Accept-Ranges': 'bytes'}
+* [test] Responde con el header {'Content-Range': 'bytes <s>-<e>/<length>'}
+* [test] Responde solo con los bytes pedidos como body
+*/
+
+// Ejecuta el servidor como 'just run -- --directory tests/data'
+
+#[tokio::test]
+async fn test_http_get_command_files_single_range() {
+    let content = fs::read(INDEX).unwrap();
+    let length = content.len();
+    let url = "http://localhost:4221/files/index.html";
+
+    let cases = [
+        ("bytes=0-9", 0, 9),
+        ("bytes=-10", length - 10, length - 1),
+        ("bytes=5-", 5, length - 1),
+    ];
+
+    for (range, start, end) in cases {
+        let http_client = Client::new();
+        let response = http_client
+            .get(url)
+            .header("Range", range)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 206);
+        assert_eq!(response.headers()["accept-ranges"], "bytes");
+        assert_eq!(
+            response.headers()["content-range"],
+            format!("bytes {}-{}/{}", start, end, length).as_str()
+        );
+        assert_eq!(
+            response.headers()["content-length"],
+            (end - start + 1).to_string().as_str()
+        );
+
+        let body = response.bytes().await.unwrap();
+        assert_eq!(&body[..], &content[start..=end]);
+    }
+}
+
+/*
+* Comprueba que el servidor responde partes de un archivo con la
+* cabecera 'Range'.
+*
+* Caso 2 (Varios rangos):
+*
+* $ curl -v -H "Range: bytes=-5,0-4,2-3" http://localhost:4221/files/index.html
+*
+* [test] Responde con el codigo de estatos 206
+* [test] Responde con el header {'Content-Type': 'multipart/byteranges; boundary=<boundary>'}
+* [test] Responde cada parte con su 'Content-Type' y 'Content-Range'
+* [test] Une los rangos superpuestos y envía las partes en orden
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_multiple_ranges() {
+    let content = fs::read(INDEX).unwrap();
+    let length = content.len();
+
+    let http_client = Client::new();
+    let response = http_client
+        .get("http://localhost:4221/files/index.html")
+        .header("Range", "bytes=-5,0-4,2-3")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 206);
+
+    let content_type = response.headers()["content-type"].to_str().unwrap();
+    let boundary = content_type
+        .strip_prefix("multipart/byteranges; boundary=")
+        .unwrap()
+        .to_string();
+
+    let body = response.bytes().await.unwrap();
+
+    let mut expected = Vec::new();
+    for (start, end) in [(0, 4), (length - 5, length - 1)] {
+        expected.extend_from_slice(
+            format!(
+                "\r\n--{}\r\nContent-Type: text/html; charset=utf-8\r\n\
+                 Content-Range: bytes {}-{}/{}\r\n\r\n",
+                boundary, start, end, length
+            )
+            .as_bytes(),
+        );
+        expected.extend_from_slice(&content[start..=end]);
+    }
+    expected.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    assert_eq!(&body[..], &expected[..]);
+}
+
+/*
+* Comprueba que el servidor responde partes de un archivo con la
+* cabecera 'Range'.
+*
+* Caso 3 (Rangos repetidos):
+*
+* $ curl -v -H "Range: bytes=0-,0-,...,0-" \
+*   http://localhost:4221/files/index.html
+*
+* [test] Responde con el codigo de estatos 206
+* [test] Responde con el header {'Content-Range': 'bytes 0-<length - 1>/<length>'}
+* [test] Responde el archivo una sola vez como body
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_repeated_ranges() {
+    let content = fs::read(INDEX).unwrap();
+    let length = content.len();
+    let range = format!("bytes={}", vec!["0-"; 32].join(","));
+
+    let http_client = Client::new();
+    let response = http_client
+        .get("http://localhost:4221/files/index.html")
+        .header("Range", range)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 206);
+    assert_eq!(
+        response.headers()["content-range"],
+        format!("bytes 0-{}/{}", length - 1, length).as_str()
+    );
+    assert_eq!(&response.bytes().await.unwrap()[..], &content[..]);
+}
+
+/*
+* Comprueba que el servidor responde partes de un archivo con la
+* cabecera 'Range'.
+*
+* Caso 4 (Rango fuera del archivo):
+*
+* $ curl -v -H "Range: bytes=99999-" http://localhost:4221/files/index.html
+*
+* [test] Responde con el codigo de estatos 416
+* [test] Responde con el header 'Content-Range' sin rango, solo con el largo
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_range_not_satisfiable() {
+    let length = fs::metadata(INDEX).unwrap().len();
+
+    let http_client = Client::new();
+    let response = http_client
+        .get("http://localhost:4221/files/index.html")
+        .header("Range", "bytes=99999-")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 416);
+    assert_eq!(
+        response.headers()["content-range"],
+        format!("bytes */{}", length).as_str()
+    );
+}
+
+/*
+* Comprueba que el servidor responde partes de un archivo con la
+* cabecera 'Range'.
+*
+* Caso 5 (If-Range):
+*
+* $ curl -v -H "Range: bytes=0-9" -H 'If-Range: "<etag>"' \
+*     http://localhost:4221/files/index.html
+*
+* [test] Responde con el codigo de estatos 206 si el ETag coincide
+* [test] Responde con el codigo de estatos 200 y el archivo completo si
+*        el ETag o la fecha no coinciden
+*/
+
+#[tokio::test]
+async fn test_http_get_command_files_if_range() {
+    let url = "http://localhost:4221/files/index.html";
+    let content = fs::read(INDEX).unwrap();
+
+    let http_client = Client::new();
+    let response = http_client.get(url).send().await.unwrap();
+    let etag = response.headers()["etag"].clone();
+    let last_modified = response.headers()["last-modified"].clone();
+
+    let cases = [
+        (etag.to_str().unwrap(), 206),
+        (last_modified.to_str().unwrap(), 206),
+        ("\"otro-etag\"", 200),
+        ("W/\"otro-etag\"", 200),
+        ("Thu, 01 Jan 1970 00:00:00 GMT", 200),
+    ];
+
+    for (if_range, status) in cases {
+        let response = http_client
+            .get(url)
+            .header("Range", "bytes=0-9")
+            .header("If-Range", if_range)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status, "{}", if_range);
+
+        let body = response.bytes().await.unwrap();
+        match status {
+            206 => assert_eq!(&body[..], &content[..10]),
+            _ => assert_eq!(&body[..], &content[..]),
+        }
+    }
+}
@@ -1,4 +1,6 @@
 use reqwest::Client;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
 
 /*
 * Comprueba que el servidor responde al comando 'user-agent'
@@ -40,3 +42,28 @@ async fn test_http_server_command_user_agent() {
         assert_eq!(body, *user_agent);
     }
 }
+
+/*
+* Comprueba que el servidor responde al comando 'user-agent'
+* aunque la solicitud no tenga la cabecera 'User-Agent'.
+*
+* [test] Responde con el codigo de estatos 200
+* [test] Responde con el body vacío
+*
+* Example:
+* $ curl -v http://localhost:4221/user-agent -H "User-Agent:"
+*/
+
+#[tokio::test]
+async fn test_http_server_command_user_agent_without_header() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = "GET /user-agent HTTP/1.1\r\nConnection: close\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.ends_with("\r\n\r\n"));
+}
@@ -0,0 +1,14 @@
+#[path = "methods/not_allowed.rs"]
+mod methods_not_allowed;
+
+#[path = "methods/options.rs"]
+mod methods_options;
+
+#[path = "methods/not_implemented.rs"]
+mod methods_not_implemented;
+
+#[path = "methods/head.rs"]
+mod methods_head;
+
+#[path = "methods/read_only.rs"]
+mod methods_read_only;
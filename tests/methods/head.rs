@@ -0,0 +1,70 @@
+use std::fs;
+
+use reqwest::Client;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/*
+* Comprueba que el servidor responde HEAD en las rutas GET con las
+* mismas cabeceras y sin body.
+*
+* [test] Responde con el codigo de estatos 200
+* [test] Responde con el 'Content-Length' del archivo
+* [test] No envía ningún byte del body
+*
+* Example:
+* $ curl -v -I http://localhost:4221/files/Rick_and_Morty.jpg
+*/
+
+#[tokio::test]
+async fn test_http_head_files() {
+    let name_file = "Rick_and_Morty.jpg";
+    let url = format!("http://localhost:4221/files/{}", name_file);
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let file_size = fs::metadata(server_folder.join(name_file)).unwrap().len();
+
+    let http_client = Client::new();
+    let response = http_client.head(&url).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-length"], file_size.to_string());
+
+    let body = response.bytes().await.unwrap();
+    assert!(body.is_empty());
+}
+
+/*
+* Comprueba que después de una respuesta HEAD la conexión sigue
+* sincronizada: la siguiente respuesta empieza justo al terminar
+* las cabeceras.
+*
+* [test] La respuesta a HEAD no tiene body
+* [test] La respuesta siguiente llega completa
+*/
+
+#[tokio::test]
+async fn test_http_head_keeps_connection_in_sync() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let requests = [
+        "HEAD /echo/strawberry HTTP/1.1\r\n\r\n",
+        "GET /echo/ok HTTP/1.1\r\nConnection: close\r\n\r\n",
+    ];
+    stream
+        .write_all(requests.concat().as_bytes())
+        .await
+        .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    let (head, next) = response.split_once("\r\n\r\n").unwrap();
+
+    assert!(head.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(head.contains("Content-Length: 10"));
+    assert!(next.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(next.ends_with("\r\n\r\nok"));
+}
@@ -0,0 +1,36 @@
+use reqwest::{Client, Method};
+
+/*
+* Comprueba que el servidor responde estatus 405 cuando la ruta
+* existe pero no admite el método de la solicitud.
+*
+* [test] Responde con el codigo de estatos 405
+* [test] Responde con el header 'Allow' con los métodos de la ruta
+*
+* Example:
+* $ curl -v -X POST http://localhost:4221/echo/x
+*/
+
+#[tokio::test]
+async fn test_http_method_not_allowed() {
+    let cases = [
+        (Method::POST, "echo/x", "GET, HEAD, OPTIONS"),
+        (Method::POST, "user-agent", "GET, HEAD, OPTIONS"),
+        (
+            Method::PATCH,
+            "files/a",
+            "GET, HEAD, POST, PUT, DELETE, OPTIONS",
+        ),
+    ];
+    let host = String::from("http://localhost:4221");
+
+    for (method, path, allow) in cases {
+        let url = format!("{}/{}", host, path);
+
+        let http_client = Client::new();
+        let response = http_client.request(method, &url).send().await.unwrap();
+
+        assert_eq!(response.status(), 405, "path: {}", path);
+        assert_eq!(response.headers()["allow"], allow);
+    }
+}
@@ -0,0 +1,29 @@
+use reqwest::{Client, Method};
+
+/*
+* Comprueba que el servidor responde estatus 501 con los métodos
+* que no reconoce, aunque la ruta exista.
+*
+* [test] Responde con el codigo de estatos 501
+*
+* Example:
+* $ curl -v -X PROPFIND http://localhost:4221/files/index.html
+*/
+
+#[tokio::test]
+async fn test_http_method_not_implemented() {
+    let methods = ["PROPFIND", "get", "BREW"];
+    let url = "http://localhost:4221/files/index.html";
+
+    for method in methods {
+        let method = Method::from_bytes(method.as_bytes()).unwrap();
+
+        let http_client = Client::new();
+        let response = http_client.request(method, url).send().await.unwrap();
+
+        assert_eq!(response.status(), 501);
+
+        let body = response.text().await.unwrap();
+        assert_eq!(body, "El servidor no reconoce el método de la solicitud");
+    }
+}
@@ -0,0 +1,65 @@
+use reqwest::{Client, Method};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/*
+* Comprueba que el servidor responde 'OPTIONS' en cualquier ruta
+* existente con los métodos que admite.
+*
+* [test] Responde con el codigo de estatos 204
+* [test] Responde con el header 'Allow' con los métodos de la ruta
+*
+* Example:
+* $ curl -v -X OPTIONS http://localhost:4221/files/index.html
+*/
+
+#[tokio::test]
+async fn test_http_options_path() {
+    let cases = [
+        ("files/index.html", "GET, HEAD, POST, PUT, DELETE, OPTIONS"),
+        ("echo/abc", "GET, HEAD, OPTIONS"),
+    ];
+    let host = String::from("http://localhost:4221");
+
+    for (path, allow) in cases {
+        let url = format!("{}/{}", host, path);
+
+        let http_client = Client::new();
+        let response = http_client
+            .request(Method::OPTIONS, &url)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 204, "path: {}", path);
+        assert_eq!(response.headers()["allow"], allow);
+    }
+}
+
+/*
+* Comprueba que el servidor responde 'OPTIONS *' con todos los
+* métodos que admite.
+*
+* [test] Responde con el codigo de estatos 204
+* [test] Responde con el header 'Allow' sin 'Content-Length'
+*
+* Example:
+* $ curl -v -X OPTIONS --request-target '*' http://localhost:4221
+*/
+
+#[tokio::test]
+async fn test_http_options_asterisk() {
+    let mut stream = TcpStream::connect("localhost:4221").await.unwrap();
+
+    let request = "OPTIONS * HTTP/1.1\r\nConnection: close\r\n\r\n";
+    stream.write_all(request.as_bytes()).await.unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+
+    assert!(response.starts_with("HTTP/1.1 204 No Content\r\n"));
+    assert!(
+        response.contains("Allow: GET, HEAD, POST, PUT, DELETE, OPTIONS\r\n")
+    );
+    assert!(!response.contains("Content-Length"));
+}
@@ -0,0 +1,86 @@
+use std::process::{Child, Command};
+use std::time::Duration;
+
+use reqwest::{Client, Method};
+use tokio::net::TcpStream;
+
+/*
+* Servidor propio, iniciado con '--read-only' en otro puerto, que se
+* detiene al terminar el test.
+*/
+struct ReadOnlyServer(Child);
+
+impl ReadOnlyServer {
+    async fn start(port: u16) -> ReadOnlyServer {
+        let child =
+            Command::new(env!("CARGO_BIN_EXE_http-server-starter-rust"))
+                .args(["--directory", "tests/data", "--read-only"])
+                .args(["--port", &port.to_string()])
+                .spawn()
+                .unwrap();
+        let server = ReadOnlyServer(child);
+
+        for _ in 0..50 {
+            if TcpStream::connect(("localhost", port)).await.is_ok() {
+                return server;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        panic!("El servidor con '--read-only' no arrancó");
+    }
+}
+
+impl Drop for ReadOnlyServer {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/*
+* Comprueba que con '--read-only' todos los métodos anuncian el mismo
+* 'Allow' para un archivo.
+*
+* $ just run -- --directory tests/data --read-only --port 4222
+* $ curl -v -X OPTIONS http://localhost:4222/files/index.html
+* $ curl -v -X PATCH http://localhost:4222/files/index.html
+*
+* [test] Responde 'OPTIONS' con el codigo de estatos 204
+* [test] Responde 'PATCH', 'PUT', 'POST' y 'DELETE' con el codigo de estatos 405
+* [test] Responde siempre con el header {'Allow': 'GET, HEAD, OPTIONS'}
+* [test] El archivo no se modifica
+*/
+
+#[tokio::test]
+async fn test_http_read_only_allow() {
+    let port = 4222;
+    let _server = ReadOnlyServer::start(port).await;
+    let url = format!("http://localhost:{}/files/index.html", port);
+    let content = std::fs::read("tests/data/index.html").unwrap();
+
+    let cases = [
+        (Method::OPTIONS, 204),
+        (Method::PATCH, 405),
+        (Method::PUT, 405),
+        (Method::POST, 405),
+        (Method::DELETE, 405),
+    ];
+
+    let http_client = Client::new();
+    for (method, status) in cases {
+        let response = http_client
+            .request(method.clone(), &url)
+            .body("mango")
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), status, "{}", method);
+        assert_eq!(response.headers()["allow"], "GET, HEAD, OPTIONS");
+    }
+
+    let response = http_client.get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(std::fs::read("tests/data/index.html").unwrap(), content);
+}
@@ -2,6 +2,8 @@ use std::fs;
 use std::io::Write;
 use std::path::Path;
 
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use reqwest::Client;
 
 /*
@@ -77,3 +79,296 @@ async fn test_http_post_command_files_exist_file() {
     fs::remove_file(path_input_file).unwrap();
     fs::remove_file(result_path).unwrap();
 }
+
+/*
+* Comprueba que el comando 'files' no permite escribir archivos fuera
+* del directorio del servidor a través de un enlace simbólico.
+*
+* Caso 2 (Directory traversal):
+*
+* $ curl -v -X POST http://localhost:4221/files/link_to_tmp/pwned.txt -d 'x'
+*
+* [test] Responde con el codigo de estatos 403
+* [test] No crea el archivo en el destino del enlace
+*/
+
+#[tokio::test]
+async fn test_http_post_command_files_symlink_outside_directory() {
+    let name_link = "link_to_tmp";
+    let name_file = format!("pwned_{}.txt", std::process::id());
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let link_path = server_folder.join(name_link);
+    let temp_dir = std::env::temp_dir();
+
+    let _ = fs::remove_file(&link_path);
+    std::os::unix::fs::symlink(&temp_dir, &link_path).unwrap();
+
+    let host = String::from("http://localhost:4221");
+    let url = format!("{}/files/{}/{}", host, name_link, name_file);
+
+    let http_client = Client::new();
+    let response = http_client.post(&url).body("x").send().await.unwrap();
+
+    fs::remove_file(link_path).unwrap();
+
+    assert_eq!(response.status(), 403);
+    assert!(!temp_dir.join(name_file).exists());
+}
+
+/*
+* Comprueba que el comando 'files' guarda el contenido descomprimido
+* cuando el body llega con 'Content-Encoding'.
+*
+* Caso 3 (Body comprimido):
+*
+* $ echo -n 'mango banana' | gzip | curl -v -X POST \
+*   http://localhost:4221/files/mango_banana.txt \
+*   -H "Content-Encoding: gzip" --data-binary @-
+*
+* [test] Responde con el codigo de estatos 201
+* [test] Guarda el archivo descomprimido
+*/
+
+#[tokio::test]
+async fn test_http_post_command_files_gzip_body() {
+    let name_file = format!("gzip_body_{}.txt", std::process::id());
+    let content = "mango banana ".repeat(20);
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(content.as_bytes()).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    let host = String::from("http://localhost:4221");
+    let url = format!("{}/files/{}", host, name_file);
+
+    let http_client = Client::new();
+    let response = http_client
+        .post(&url)
+        .header("Content-Encoding", "gzip")
+        .body(compressed)
+        .send()
+        .await
+        .unwrap();
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let result_path = server_folder.join(name_file);
+
+    assert_eq!(response.status(), 201);
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), content);
+
+    fs::remove_file(result_path).unwrap();
+}
+
+/*
+* Comprueba que el servidor rechaza un body con una codificación
+* que no soporta.
+*
+* Caso 4 (Content-Encoding no soportado):
+*
+* $ curl -v -X POST http://localhost:4221/files/brotli.txt \
+*   -H "Content-Encoding: br" -d 'x'
+*
+* [test] Responde con el codigo de estatos 415
+* [test] Responde con el header {'Accept-Encoding': 'gzip, deflate'}
+* [test] No crea el archivo
+*/
+
+#[tokio::test]
+async fn test_http_post_command_files_unsupported_encoding() {
+    let name_file = format!("brotli_{}.txt", std::process::id());
+
+    let host = String::from("http://localhost:4221");
+    let url = format!("{}/files/{}", host, name_file);
+
+    let http_client = Client::new();
+    let response = http_client
+        .post(&url)
+        .header("Content-Encoding", "br")
+        .body("x")
+        .send()
+        .await
+        .unwrap();
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+
+    assert_eq!(response.status(), 415);
+    assert_eq!(response.headers()["accept-encoding"], "gzip, deflate");
+    assert!(!server_folder.join(name_file).exists());
+}
+
+/*
+* Comprueba que el servidor rechaza un archivo cuyo 'Content-Type'
+* no corresponde a su extensión.
+*
+* Caso 5 (Content-Type incorrecto):
+*
+* $ curl -v -X POST http://localhost:4221/files/image.png \
+*   -H "Content-Type: text/html" -d '<html></html>'
+*
+* [test] Responde con el codigo de estatos 415
+* [test] No crea el archivo
+*/
+
+#[tokio::test]
+async fn test_http_post_command_files_content_type_mismatch() {
+    let name_file = format!("mismatch_{}.png", std::process::id());
+
+    let host = String::from("http://localhost:4221");
+    let url = format!("{}/files/{}", host, name_file);
+
+    let http_client = Client::new();
+    let response = http_client
+        .post(&url)
+        .header("Content-Type", "text/html")
+        .body("<html></html>")
+        .send()
+        .await
+        .unwrap();
+
+    let this_file = file!();
+    let this_file = std::path::Path::new(this_file);
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+
+    assert_eq!(response.status(), 415);
+    assert!(!server_folder.join(name_file).exists());
+}
+
+/*
+* Comprueba que el comando 'files' no reemplaza un archivo que cambió
+* desde que el cliente lo leyó.
+*
+* Caso 6 (Precondiciones):
+*
+* $ curl -v -X POST http://localhost:4221/files/lost_update.txt \
+*   -H "If-None-Match: *" -d 'v1'
+* $ curl -v -X POST http://localhost:4221/files/lost_update.txt \
+*   -H 'If-Match: "<etag>"' -d 'v2'
+*
+* [test] Con 'If-None-Match: *' solo crea el archivo si no existe
+* [test] Con 'If-Match' solo lo reemplaza si el ETag sigue siendo el actual
+* [test] Responde con el codigo de estatos 412 sin modificar el archivo
+*/
+
+#[tokio::test]
+async fn test_http_post_command_files_preconditions() {
+    let name_file = format!("lost_update_{}.txt", std::process::id());
+    let url = format!("http://localhost:4221/files/{}", name_file);
+
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let result_path = server_folder.join(&name_file);
+
+    let http_client = Client::new();
+    let post = |body: &'static str, header: (&'static str, String)| {
+        http_client
+            .post(&url)
+            .header(header.0, header.1)
+            .body(body)
+            .send()
+    };
+
+    let response = post("v1", ("If-None-Match", "*".into())).await.unwrap();
+    assert_eq!(response.status(), 201);
+
+    let response = post("v2", ("If-None-Match", "*".into())).await.unwrap();
+    assert_eq!(response.status(), 412);
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), "v1");
+
+    // Aunque se acaba de escribir, el ETag es fuerte y If-Match lo acepta
+    let response = http_client.get(&url).send().await.unwrap();
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+    assert!(!etag.starts_with("W/"));
+
+    let response = post("v2", ("If-Match", etag.clone())).await.unwrap();
+    assert_eq!(response.status(), 201);
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), "v2");
+
+    // Otro cliente con el ETag anterior no pisa el cambio
+    let response = post("v3", ("If-Match", etag)).await.unwrap();
+    assert_eq!(response.status(), 412);
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), "v2");
+
+    fs::remove_file(result_path).unwrap();
+}
+
+/*
+* Comprueba que el comando 'files' guarda archivos binarios sin
+* modificarlos.
+*
+* Caso 7 (Archivo binario):
+*
+* $ curl -v -X POST http://localhost:4221/files/rick_copy.jpg \
+*   --data-binary @tests/data/Rick_and_Morty.jpg
+* $ curl -v http://localhost:4221/files/rick_copy.jpg
+*
+* [test] Responde con el codigo de estatos 201
+* [test] Guarda exactamente los bytes enviados
+* [test] Responde con los mismos bytes al pedir el archivo
+*/
+
+#[tokio::test]
+async fn test_http_post_command_files_binary_round_trip() {
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let image = fs::read(server_folder.join("Rick_and_Morty.jpg")).unwrap();
+
+    let name_file = format!("rick_copy_{}.jpg", std::process::id());
+    let url = format!("http://localhost:4221/files/{}", name_file);
+    let result_path = server_folder.join(&name_file);
+
+    let http_client = Client::new();
+    let response = http_client
+        .post(&url)
+        .header("Content-Type", "image/jpeg")
+        .body(image.clone())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 201);
+    assert_eq!(fs::read(&result_path).unwrap(), image);
+
+    let response = http_client.get(&url).send().await.unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.bytes().await.unwrap().to_vec(), image);
+
+    fs::remove_file(result_path).unwrap();
+}
+
+/*
+* Comprueba que el comando 'files' acepta un body vacío.
+*
+* Caso 8 (Body vacío):
+*
+* $ curl -v -X POST http://localhost:4221/files/empty.txt -H "Content-Length: 0"
+*
+* [test] Responde con el codigo de estatos 201
+* [test] Crea el archivo vacío, reemplazando el contenido anterior
+*/
+
+#[tokio::test]
+async fn test_http_post_command_files_empty_body() {
+    let name_file = format!("empty_{}.txt", std::process::id());
+    let url = format!("http://localhost:4221/files/{}", name_file);
+
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+    let result_path = server_folder.join(&name_file);
+    fs::write(&result_path, "mango").unwrap();
+
+    let http_client = Client::new();
+    let response = http_client.post(&url).send().await.unwrap();
+
+    assert_eq!(response.status(), 201);
+    assert_eq!(fs::read(&result_path).unwrap(), b"");
+
+    fs::remove_file(result_path).unwrap();
+}
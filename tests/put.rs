@@ -0,0 +1,2 @@
+#[path = "put/command_files.rs"]
+mod put_command_files;
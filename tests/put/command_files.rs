@@ -0,0 +1,233 @@
+use std::fs;
+use std::path::Path;
+
+use reqwest::Client;
+
+/*
+* Comprueba que el comando 'files' crea y reemplaza archivos con PUT.
+*
+* Caso 1 (Crear y reemplazar):
+*
+* $ curl -v -X PUT http://localhost:4221/files/mango.txt -d 'mango'
+* $ curl -v -X PUT http://localhost:4221/files/mango.txt -d 'banana'
+*
+* [test] Responde con el codigo de estatos 201 si el archivo no existía
+* [test] Responde con el header {'Location': '/files/<nombre>'}
+* [test] Responde con el codigo de estatos 204 si lo reemplazó
+* [test] Guarda el body como contenido del archivo
+* [test] El 'ETag' que devuelve sirve enseguida para 'If-Match'
+* [test] El 'ETag' de la respuesta comprimida también sirve para 'If-Match'
+*/
+
+// Ejecuta el servidor como 'just run -- --directory tests/data'
+
+#[tokio::test]
+async fn test_http_put_command_files_create_and_replace() {
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+
+    let name_file = format!("put mango {}.txt", std::process::id());
+    let url = format!("http://localhost:4221/files/{}", name_file);
+    let result_path = server_folder.join(&name_file);
+
+    let http_client = Client::new();
+    let response = http_client.put(&url).body("mango").send().await.unwrap();
+
+    assert_eq!(response.status(), 201);
+    assert_eq!(
+        response.headers()["location"],
+        format!("/files/{}", name_file.replace(' ', "%20")).as_str()
+    );
+    assert!(response.headers().contains_key("etag"));
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), "mango");
+
+    // Repetir la solicitud reemplaza el archivo con el mismo contenido
+    for body in ["banana", "banana"] {
+        let response = http_client.put(&url).body(body).send().await.unwrap();
+
+        assert_eq!(response.status(), 204);
+        assert!(!response.headers().contains_key("location"));
+        assert_eq!(fs::read_to_string(&result_path).unwrap(), body);
+    }
+
+    let response = http_client.put(&url).body("kiwi").send().await.unwrap();
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+    assert!(!etag.starts_with("W/"));
+
+    let response = http_client
+        .put(&url)
+        .header("If-Match", &etag)
+        .body("pera")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), "pera");
+
+    let response = http_client
+        .get(&url)
+        .header("Accept-Encoding", "gzip")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+    let etag = response.headers()["etag"].to_str().unwrap().to_string();
+    assert!(!etag.starts_with("W/"));
+    assert!(etag.ends_with("-gzip\""));
+
+    let response = http_client
+        .put(&url)
+        .header("If-Match", &etag)
+        .body("uva")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+    assert_eq!(fs::read_to_string(&result_path).unwrap(), "uva");
+
+    fs::remove_file(result_path).unwrap();
+}
+
+/*
+* Comprueba que el comando 'files' rechaza los PUT que no se pueden
+* completar.
+*
+* Caso 2 (Errores):
+*
+* $ curl -v -X PUT http://localhost:4221/files/no_dir/mango.txt -d 'mango'
+* $ curl -v -X PUT http://localhost:4221/files/index.html \
+*   -H 'If-Match: "otro"' -d 'mango'
+*
+* [test] Responde con el codigo de estatos 409 si falta el directorio
+* [test] Responde con el codigo de estatos 409 si la ruta es un directorio
+* [test] Responde con el codigo de estatos 412 si no se cumple If-Match
+* [test] Responde con el codigo de estatos 400 si el nombre tiene una "/"
+* codificada
+*/
+
+#[tokio::test]
+async fn test_http_put_command_files_errors() {
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+
+    let host = "http://localhost:4221";
+    let http_client = Client::new();
+
+    let cases = [
+        ("/files/no_dir_put/mango.txt", None, 409),
+        ("/files/", None, 409),
+        ("/files/index.html", Some("\"otro\""), 412),
+        ("/files/sub%2Fmango.txt", None, 400),
+    ];
+
+    for (path, if_match, status) in cases {
+        let mut request = http_client.put(format!("{}{}", host, path));
+        if let Some(if_match) = if_match {
+            request = request.header("If-Match", if_match);
+        }
+
+        let response = request.body("mango").send().await.unwrap();
+
+        assert_eq!(response.status(), status, "{}", path);
+    }
+
+    assert!(!server_folder.join("no_dir_put").exists());
+    assert!(fs::read_to_string(server_folder.join("index.html"))
+        .unwrap()
+        .contains("<html"));
+}
+
+/*
+* Comprueba que el comando 'files' guarda archivos binarios con PUT sin
+* modificarlos.
+*
+* Caso 3 (Archivo binario):
+*
+* $ curl -v -X PUT http://localhost:4221/files/rick_put.jpg \
+*   --data-binary @tests/data/Rick_and_Morty.jpg
+*
+* [test] Responde con el codigo de estatos 201
+* [test] Responde con los mismos bytes al pedir el archivo
+*/
+
+#[tokio::test]
+async fn test_http_put_command_files_binary_round_trip() {
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+
+    let image = fs::read(server_folder.join("Rick_and_Morty.jpg")).unwrap();
+    let name_file = format!("rick_put_{}.jpg", std::process::id());
+    let url = format!("http://localhost:4221/files/{}", name_file);
+
+    let http_client = Client::new();
+    let response = http_client
+        .put(&url)
+        .body(image.clone())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 201);
+
+    let response = http_client.get(&url).send().await.unwrap();
+    assert_eq!(response.bytes().await.unwrap().to_vec(), image);
+
+    fs::remove_file(server_folder.join(&name_file)).unwrap();
+}
+
+/*
+* Comprueba que el comando 'files' crea el archivo una sola vez aunque
+* lleguen varios PUT con 'If-None-Match: *' a la vez.
+*
+* Caso 4 (Creación simultánea):
+*
+* $ for body in 0 1 2 3 4 5 6 7; do
+*     curl -v -X PUT http://localhost:4221/files/once.txt \
+*       -H "If-None-Match: *" -d "$body" &
+*   done
+*
+* [test] Solo una solicitud responde con el codigo de estatos 201
+* [test] Las demás responden con el codigo de estatos 412
+* [test] El archivo guarda el body de la que respondió 201
+*/
+
+#[tokio::test]
+async fn test_http_put_command_files_create_only_once() {
+    let this_file = Path::new(file!());
+    let server_folder = this_file.ancestors().nth(2).unwrap().join("data");
+
+    let name_file = format!("put_once_{}.txt", std::process::id());
+    let url = format!("http://localhost:4221/files/{}", name_file);
+
+    let http_client = Client::new();
+    let mut requests = tokio::task::JoinSet::new();
+    for body in 0..8 {
+        let request = http_client
+            .put(&url)
+            .header("If-None-Match", "*")
+            .body(body.to_string());
+
+        requests.spawn(async move {
+            let response = request.send().await.unwrap();
+            (body, response.status())
+        });
+    }
+
+    let mut created = Vec::new();
+    while let Some(result) = requests.join_next().await {
+        let (body, status) = result.unwrap();
+        match status.as_u16() {
+            201 => created.push(body),
+            status => assert_eq!(status, 412),
+        }
+    }
+
+    assert_eq!(created.len(), 1);
+    let result_path = server_folder.join(&name_file);
+    assert_eq!(
+        fs::read_to_string(&result_path).unwrap(),
+        created[0].to_string()
+    );
+
+    fs::remove_file(result_path).unwrap();
+}